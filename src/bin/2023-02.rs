@@ -1,6 +1,6 @@
 use regex::Regex;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 pub fn parse_part1(input: &str) -> Vec<u32> {
     let max_counts = [12, 13, 14];
@@ -140,13 +140,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2286));
     }
 }
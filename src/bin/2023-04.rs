@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2023, 4, parse);
 
 pub struct Card {
     pub card_number: u32,
@@ -46,8 +46,7 @@ pub fn parse(input: &str) -> Vec<Card> {
     winning_numbers
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards = parse(input);
+pub fn part_one(cards: &[Card]) -> Option<u32> {
     let card_points = cards.iter().map(|card| {
         let overlap: Vec<&u32> = card
             .check_numbers
@@ -66,8 +65,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(card_points.sum::<u32>())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = parse(input);
+pub fn part_two(cards: &[Card]) -> Option<u32> {
     let mut card_counts: HashMap<u32, u32> = HashMap::new();
     // the compiler was complaining about trying to do this with HashMap::From()
     // so here we are in a loop
@@ -107,13 +105,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(30));
     }
 }
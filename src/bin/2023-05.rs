@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2023, 5, parse);
 
 pub struct AlmanacMap {
     pub source_ranges: Vec<Range<u64>>,
@@ -55,14 +55,13 @@ pub fn parse(input: &str) -> (Seeds, Vec<AlmanacMap>) {
     (Seeds { seed_numbers }, maps)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (seeds, maps) = parse(input);
+pub fn part_one((seeds, maps): &(Seeds, Vec<AlmanacMap>)) -> Option<u64> {
     let locations: Vec<u64> = seeds
         .seed_numbers
         .iter()
         .map(|seed| {
             let mut location = *seed;
-            for map in &maps {
+            for map in maps {
                 for (index, source_range) in map.source_ranges.iter().enumerate() {
                     if source_range.contains(&location) {
                         let destination_range = &map.destination_ranges[index];
@@ -81,8 +80,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(*locations.iter().min().unwrap())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (seeds, maps) = parse(input);
+pub fn part_two((seeds, maps): &(Seeds, Vec<AlmanacMap>)) -> Option<u64> {
     let mut seed_ranges: Vec<Range<u64>> = seeds
         .seed_numbers
         .chunks_exact(2)
@@ -142,13 +140,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 }
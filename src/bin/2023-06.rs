@@ -1,7 +1,7 @@
 use num_traits::{Num, NumCast};
 use std::iter::zip;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 pub struct Race<T> {
     pub time: T,
@@ -86,13 +86,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(71503));
     }
 }
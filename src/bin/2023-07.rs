@@ -3,12 +3,12 @@ extern crate lazy_static;
 
 use std::{cmp::Ordering, collections::HashMap, fmt::Display};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 lazy_static! {
     static ref CARD_ORDER_PART_1: HashMap<String, usize> = {
         let mut m = HashMap::new();
-        let card_order = &vec![
+        let card_order = &[
             "A", "K", "Q", "J", "T", "9", "8", "7", "6", "5", "4", "3", "2",
         ];
         for (index, card) in card_order.iter().enumerate() {
@@ -21,7 +21,7 @@ lazy_static! {
 lazy_static! {
     static ref CARD_ORDER_PART_2: HashMap<String, usize> = {
         let mut m = HashMap::new();
-        let card_order = &vec![
+        let card_order = &[
             "A", "K", "Q", "T", "9", "8", "7", "6", "5", "4", "3", "2", "J",
         ];
         for (index, card) in card_order.iter().enumerate() {
@@ -306,13 +306,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(7190));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(7460));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(2023, 8, parse);
 
 pub struct NodeMap {
     pub nodes: HashMap<String, Vec<String>>,
@@ -34,7 +34,7 @@ pub fn parse(input: &str) -> (Vec<usize>, NodeMap) {
 
 pub fn get_step_count(
     mut current_node_name: String,
-    instructions: &Vec<usize>,
+    instructions: &[usize],
     node_map: &NodeMap,
 ) -> u64 {
     let mut instruction_index = 0;
@@ -69,23 +69,19 @@ pub fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
     a
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (instructions, node_map) = parse(input);
-
+pub fn part_one((instructions, node_map): &(Vec<usize>, NodeMap)) -> Option<u64> {
     let nodes_to_process = node_map.nodes.keys().filter(|key| key.ends_with('A'));
     let step_count: Vec<u64> = nodes_to_process
-        .map(|node_name| get_step_count(node_name.to_string(), &instructions, &node_map))
+        .map(|node_name| get_step_count(node_name.to_string(), instructions, node_map))
         .collect();
 
     Some(step_count[0])
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (instructions, node_map) = parse(input);
-
+pub fn part_two((instructions, node_map): &(Vec<usize>, NodeMap)) -> Option<u64> {
     let nodes_to_process = node_map.nodes.keys().filter(|key| key.ends_with('A'));
     let step_count = nodes_to_process
-        .map(|node_name| get_step_count(node_name.to_string(), &instructions, &node_map));
+        .map(|node_name| get_step_count(node_name.to_string(), instructions, node_map));
 
     let lcm = step_count.fold(1, least_common_multiple);
 
@@ -98,17 +94,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result = part_one(&parse(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 1,
+        )));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result = part_two(&parse(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 2,
+        )));
         assert_eq!(result, Some(6));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2023, 10);
 
 #[derive(Debug, PartialEq)]
 pub enum PipeType {
@@ -295,13 +295,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1));
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2023, 15);
 
 pub fn parse(input: &str) -> Vec<Vec<u64>> {
     let initialization_sequence = input
@@ -65,7 +65,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 .and_modify(|e| *e = lens)
                 .or_insert(lens);
 
-            if boxes.get(&box_number).is_none() {
+            if !boxes.contains_key(&box_number) {
                 boxes.entry(box_number).or_default().push(values.clone());
             } else if !boxes.get(&box_number).unwrap().contains(&values) {
                 boxes.get_mut(&box_number).unwrap().push(values.clone());
@@ -108,13 +108,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1320));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(145));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(19);
+advent_of_code::solution!(2023, 19, parse);
 
 pub struct Part {
     pub x: usize,
@@ -167,8 +167,7 @@ pub fn check_condition(check: &Check, part: &Part) -> bool {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (workflows, parts) = parse(input);
+pub fn part_one((workflows, parts): &(Vec<Workflow>, Vec<Part>)) -> Option<usize> {
     let start_index = workflows
         .iter()
         .position(|workflow| workflow.name == "in")
@@ -176,14 +175,14 @@ pub fn part_one(input: &str) -> Option<usize> {
 
     let total: usize = parts
         .iter()
-        .filter(|part| get_result(&workflows, part, start_index) == "A")
+        .filter(|part| get_result(workflows, part, start_index) == "A")
         .map(|part| part.x + part.m + part.a + part.s)
         .sum();
 
     Some(total)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
+pub fn part_two(_parsed: &(Vec<Workflow>, Vec<Part>)) -> Option<u32> {
     None
 }
 
@@ -193,13 +192,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, Some(19_114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", YEAR, DAY,
+        )));
         assert_eq!(result, None);
     }
 }
@@ -2,7 +2,27 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::aoc_client::get_year;
+
+/// The most puzzles an event has had.
+pub const MAX_DAYS: u8 = 25;
+
+/// The number of puzzles in the event of `year`. Events up to 2024 had 25 puzzles, later ones 12.
+pub const fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        MAX_DAYS
+    }
+}
+
+/// The number of puzzles in the event of `AOC_YEAR`, or [`MAX_DAYS`] if no year is configured.
+fn configured_days() -> u8 {
+    get_year().map_or(MAX_DAYS, days_in_year)
+}
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Use [`Day::for_year`] to also check it against the number of puzzles of a year, which is 12 since 2025.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -16,10 +36,20 @@ use std::str::FromStr;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// Creates a [`Day`] from the provided value if it's between 1 and 25,
+    /// returns [`None`] otherwise. See [`Day::for_year`] for years with fewer puzzles.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::in_range(day, MAX_DAYS)
+    }
+
+    /// Creates a [`Day`] from the provided value if it's a puzzle of `year`,
+    /// returns [`None`] otherwise.
+    pub fn for_year(year: u16, day: u8) -> Option<Self> {
+        Self::in_range(day, days_in_year(year))
+    }
+
+    fn in_range(day: u8, days: u8) -> Option<Self> {
+        if day == 0 || day > days {
             return None;
         }
         Some(Self(day))
@@ -74,26 +104,42 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {MAX_DAYS}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of the configured year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of `year`.
+pub fn all_days_of(year: u16) -> AllDays {
+    AllDays::for_year(year)
+}
+
+/// An iterator that yields every day of advent from the 1st to the last puzzle of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self {
+            current: 1,
+            last: configured_days(),
+        }
+    }
+
+    pub fn for_year(year: u16) -> Self {
+        Self {
+            current: 1,
+            last: days_in_year(year),
+        }
     }
 }
 
@@ -101,10 +147,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -115,8 +161,16 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// Pass a year as well to check the day against the number of puzzles of that year.
 #[macro_export]
 macro_rules! day {
+    ($year:expr, $day:expr) => {{
+        const _ASSERT: () = assert!(
+            $day != 0 && $day <= $crate::days_in_year($year),
+            concat!("invalid day number `", $day, "` for ", $year),
+        );
+        $crate::Day::__new_unchecked($day)
+    }};
     ($day:expr) => {{
         const _ASSERT: () = assert!(
             $day != 0 && $day <= 25,
@@ -134,7 +188,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, all_days_of, days_in_year, Day};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +221,25 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn days_depend_on_year() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+
+        assert_eq!(all_days_of(2023).count(), 25);
+        assert_eq!(all_days_of(2025).last(), Some(Day(12)));
+
+        assert_eq!(Day::for_year(2024, 25), Some(Day(25)));
+        assert_eq!(Day::for_year(2025, 12), Some(Day(12)));
+        assert_eq!(Day::for_year(2025, 13), None);
+        assert_eq!(Day::for_year(2025, 0), None);
+
+        // parsing does not depend on the year, so ledgers can hold days of every year.
+        assert_eq!("25".parse::<Day>().ok(), Some(Day(25)));
+        assert_eq!(Day::new(26), None);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
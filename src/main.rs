@@ -1,56 +1,265 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, calendar, compare, download, leaderboard, notes, read, scaffold, search, session, solve,
+    stars, stats, status, wait,
+};
+use advent_of_code::template::unlock::SystemClock;
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{env, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::template::aoc_client::{get_year, AocClientError};
+    use advent_of_code::template::bench_history::Baseline;
+    use advent_of_code::template::benchmark::{self, BenchConfig, BenchOverrides, Statistic};
+    use advent_of_code::template::commands::notes::NotesCommand;
+    use advent_of_code::template::commands::session::SessionCommand;
+    use advent_of_code::template::unlock::{self, SystemClock};
+    use advent_of_code::{days_in_year, Day};
 
     pub enum AppArguments {
+        Calendar {
+            plain: bool,
+        },
+        Compare {
+            rev_a: String,
+            rev_b: String,
+            days: Vec<Day>,
+            bench: BenchOverrides,
+            statistic: Statistic,
+        },
         Download {
             day: Day,
         },
+        Leaderboard {
+            id: Option<String>,
+            file: Option<String>,
+            force: bool,
+        },
+        Notes {
+            command: NotesCommand,
+        },
         Read {
             day: Day,
+            plain: bool,
         },
         Scaffold {
             day: Day,
         },
+        Search {
+            query: String,
+            plain: bool,
+        },
+        Session {
+            command: SessionCommand,
+        },
         Solve {
             day: Day,
             release: bool,
             time: bool,
+            bench: BenchOverrides,
             submit: Option<u8>,
+            wait: bool,
+        },
+        Stars {
+            leaderboard: bool,
+            file: Option<String>,
+            member: Option<u64>,
+        },
+        Stats {
+            member: Option<u64>,
+            file: Option<String>,
+        },
+        Status {
+            skip_run: bool,
         },
         All {
             release: bool,
             time: bool,
+            bench: BenchOverrides,
+            titles: bool,
+            statistic: Statistic,
+            baseline: Option<Baseline>,
         },
+        Wait,
+    }
+
+    /// Parses an event year. The first event was held in 2015.
+    fn parse_year(s: &str) -> Result<u16, String> {
+        match s.parse::<u16>() {
+            Ok(year) if year >= 2015 => Ok(year),
+            _ => Err(format!("\"{s}\" is not an Advent of Code year")),
+        }
+    }
+
+    /// Parses the flags that tune benchmarks of `--time`.
+    fn parse_bench(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchOverrides, Box<dyn std::error::Error>> {
+        let overrides = BenchOverrides {
+            warmup: args.opt_value_from_fn("--warmup", benchmark::parse_count)?,
+            target_time: args.opt_value_from_fn("--bench-time", benchmark::parse_time)?,
+            min_samples: args.opt_value_from_fn("--min-samples", benchmark::parse_count)?,
+            max_samples: args.opt_value_from_fn("--max-samples", benchmark::parse_count)?,
+        };
+
+        // fail up front instead of in every solution the flags are passed to,
+        // checking the flags together with the `AOC_BENCH_*` settings they override.
+        BenchConfig::from_env()?.apply(&overrides)?;
+
+        Ok(overrides)
+    }
+
+    /// Parses a day number of `AOC_YEAR` or `today`, the most recently unlocked day of `AOC_YEAR`.
+    fn parse_day(s: &str) -> Result<Day, String> {
+        if s != "today" {
+            let day: Day = s.parse().map_err(|e| format!("{e}"))?;
+            return match get_year() {
+                Some(year) => Day::for_year(year, day.into_inner()).ok_or_else(|| {
+                    format!(
+                        "expecting a day number between 1 and {} for {year}",
+                        days_in_year(year)
+                    )
+                }),
+                None => Ok(day),
+            };
+        }
+
+        let year = get_year().ok_or_else(|| AocClientError::YearNotSet.to_string())?;
+        unlock::today(year, &SystemClock)
+            .ok_or_else(|| format!("no puzzle of {year} unlocked today"))
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // `--year` overrides `AOC_YEAR` for this command and every process it spawns.
+        if let Some(year) = args.opt_value_from_fn("--year", parse_year)? {
+            env::set_var("AOC_YEAR", year.to_string());
+        }
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                bench: parse_bench(&mut args)?,
+                titles: args.contains("--titles"),
+                statistic: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+                baseline: match args.opt_value_from_str("--baseline")? {
+                    Some(commit) => Some(Baseline::Commit(commit)),
+                    None => args.contains("--compare").then_some(Baseline::Previous),
+                },
             },
+            Some("calendar") => AppArguments::Calendar {
+                plain: args.contains("--plain"),
+            },
+            Some("compare") => {
+                let bench = parse_bench(&mut args)?;
+                let statistic = args.opt_value_from_str("--stat")?.unwrap_or_default();
+                let rev_a = args.free_from_str()?;
+                let rev_b = args.free_from_str()?;
+                let mut days = vec![];
+                while let Some(day) = args.opt_free_from_fn(parse_day)? {
+                    days.push(day);
+                }
+                AppArguments::Compare {
+                    rev_a,
+                    rev_b,
+                    days,
+                    bench,
+                    statistic,
+                }
+            }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("leaderboard") => AppArguments::Leaderboard {
+                file: args.opt_value_from_str("--file")?,
+                force: args.contains("--force"),
+                id: args.opt_free_from_str()?,
             },
+            Some("notes") => {
+                let command = match args.free_from_str::<String>()?.as_str() {
+                    "report" => NotesCommand::Report,
+                    day => {
+                        let day = parse_day(day)?;
+                        let mut words: Vec<String> = vec![];
+                        while let Some(word) = args.opt_free_from_str()? {
+                            words.push(word);
+                        }
+                        if words.is_empty() {
+                            NotesCommand::Open(day)
+                        } else {
+                            NotesCommand::Append(day, words.join(" "))
+                        }
+                    }
+                };
+                AppArguments::Notes { command }
+            }
+            Some("read") => {
+                let plain = args.contains("--plain");
+                AppArguments::Read {
+                    day: args.free_from_fn(parse_day)?,
+                    plain,
+                }
+            }
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                day: args.free_from_fn(parse_day)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            Some("search") => {
+                let plain = args.contains("--plain");
+                let mut terms: Vec<String> = vec![];
+                while let Some(term) = args.opt_free_from_str()? {
+                    terms.push(term);
+                }
+                AppArguments::Search {
+                    query: terms.join(" "),
+                    plain,
+                }
+            }
+            Some("session") => {
+                AppArguments::Session {
+                    command: match args.free_from_str::<String>()?.as_str() {
+                        "set" => SessionCommand::Set(args.free_from_str()?),
+                        "show" => SessionCommand::Show,
+                        "validate" => SessionCommand::Validate,
+                        "clear" => SessionCommand::Clear,
+                        x => {
+                            eprintln!("Unknown session command: {x}. Expected set, show, validate or clear.");
+                            process::exit(1);
+                        }
+                    },
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let bench = parse_bench(&mut args)?;
+                let wait = args.contains("--wait");
+                AppArguments::Solve {
+                    day: args.free_from_fn(parse_day)?,
+                    release,
+                    submit,
+                    time,
+                    bench,
+                    wait,
+                }
+            }
+            Some("stars") => AppArguments::Stars {
+                leaderboard: args.contains("--leaderboard"),
+                file: args.opt_value_from_str("--file")?,
+                member: args.opt_value_from_str("--member")?,
             },
+            Some("stats") => AppArguments::Stats {
+                member: args.opt_value_from_str("--member")?,
+                file: args.opt_value_from_str("--file")?,
+            },
+            Some("status") => AppArguments::Status {
+                skip_run: args.contains("--no-run"),
+            },
+            Some("wait") => AppArguments::Wait,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -77,16 +286,45 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                bench,
+                titles,
+                statistic,
+                baseline,
+            } => all::handle(release, time, &bench, titles, statistic, baseline),
+            AppArguments::Calendar { plain } => calendar::handle(plain, &SystemClock),
+            AppArguments::Compare {
+                rev_a,
+                rev_b,
+                days,
+                bench,
+                statistic,
+            } => compare::handle(&rev_a, &rev_b, days, &bench, statistic),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Leaderboard { id, file, force } => leaderboard::handle(id, file, force),
+            AppArguments::Notes { command } => notes::handle(command),
+            AppArguments::Read { day, plain } => read::handle(day, plain),
             AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Search { query, plain } => search::handle(&query, plain),
+            AppArguments::Session { command } => session::handle(command),
             AppArguments::Solve {
                 day,
                 release,
                 time,
+                bench,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                wait,
+            } => solve::handle(day, release, time, &bench, submit, wait),
+            AppArguments::Stars {
+                leaderboard,
+                file,
+                member,
+            } => stars::handle(leaderboard, file, member),
+            AppArguments::Stats { member, file } => stats::handle(member, file),
+            AppArguments::Status { skip_run } => status::handle(skip_run),
+            AppArguments::Wait => wait::handle(&SystemClock),
         },
     };
 }
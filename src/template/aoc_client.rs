@@ -0,0 +1,681 @@
+/// Native client for the Advent of Code website.
+/// Fetches inputs and puzzle descriptions and posts answers using the session cookie.
+use std::{env, fmt::Display, fs, path::Path, time::Duration};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::{get_data_dir, leaderboard::get_leaderboard_path, session, verdict::Verdict};
+use crate::Day;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/michael-long88/advent_of_code_2023 via ureq";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    SessionInvalid,
+    YearNotSet,
+    BadStatus(u16),
+    Transport(String),
+    InvalidInput(InvalidInput),
+    MissingDescription,
+    LeaderboardUnavailable,
+    IoError,
+}
+
+/// Known shapes of responses that are not a puzzle input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    NotUnlocked,
+    LoggedOut,
+    Html,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the response was empty, the day is probably locked"),
+            InvalidInput::NotUnlocked => write!(f, "the puzzle has not unlocked yet"),
+            InvalidInput::LoggedOut => {
+                write!(f, "the site did not accept the session, log in again")
+            }
+            InvalidInput::Html => {
+                write!(
+                    f,
+                    "received an HTML page instead of the input, probably the login page"
+                )
+            }
+        }
+    }
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Run `cargo session set <token>` to store one."
+            ),
+            AocClientError::SessionInvalid => write!(
+                f,
+                "the session cookie is expired or invalid. Store a fresh one with `cargo session set <token>`."
+            ),
+            AocClientError::YearNotSet => write!(f, "AOC_YEAR is not set to a valid year."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::InvalidInput(reason) => write!(f, "invalid puzzle input: {reason}."),
+            AocClientError::MissingDescription => {
+                write!(f, "the puzzle page did not contain a description.")
+            }
+            AocClientError::LeaderboardUnavailable => write!(
+                f,
+                "the leaderboard was not returned. Check the leaderboard id and that you are a member."
+            ),
+            AocClientError::IoError => write!(f, "could not write output files to file system."),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+
+        Self {
+            base_url: DEFAULT_BASE_URL.into(),
+            session: session.trim().into(),
+            year,
+            agent,
+        }
+    }
+
+    /// Points the client at a different host, e.g. a local stand-in server.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Switches the client to a different event.
+    #[must_use]
+    pub fn with_year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Creates a client from the resolved session token, `AOC_YEAR` and `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = session::load().ok_or(AocClientError::SessionNotFound)?;
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        let client = Self::new(&session.token, year);
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) if !base_url.is_empty() => client.with_base_url(&base_url),
+            _ => client,
+        })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Checks that the session is logged in and returns the user name shown on the event page.
+    pub fn validate_session(&self) -> Result<String, AocClientError> {
+        let html = self.get(&format!("/{}", self.year))?;
+        USER.captures(&html)
+            .map(|c| c[1].trim().to_string())
+            .ok_or(AocClientError::SessionInvalid)
+    }
+
+    /// Fetches the personal puzzle input for a day.
+    /// The response is validated, error pages are returned as [`AocClientError::InvalidInput`].
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        let (status, body) = self.get_raw(&format!("{}/input", self.day_path(day)))?;
+        validate_input(&body).map_err(AocClientError::InvalidInput)?;
+
+        if status != 200 {
+            return Err(AocClientError::BadStatus(status));
+        }
+
+        Ok(body)
+    }
+
+    /// Fetches the puzzle description for a day and converts it to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_path(day))?;
+        let articles: Vec<String> = extract_articles(&html)
+            .iter()
+            .map(|article| html_to_markdown(article, &self.base_url))
+            .collect();
+
+        if articles.is_empty() {
+            return Err(AocClientError::MissingDescription);
+        }
+
+        Ok(articles.join("\n"))
+    }
+
+    /// Fetches the JSON of a private leaderboard.
+    pub fn get_leaderboard(&self, id: &str) -> Result<String, AocClientError> {
+        let json = self.get(&format!(
+            "/{}/leaderboard/private/view/{id}.json",
+            self.year
+        ))?;
+
+        // the site redirects to an HTML page if the leaderboard is not accessible.
+        if !json.trim_start().starts_with('{') {
+            return Err(AocClientError::LeaderboardUnavailable);
+        }
+
+        Ok(json)
+    }
+
+    /// Posts an answer and returns the verdict derived from the response message.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Verdict, AocClientError> {
+        let url = format!("{}{}/answer", self.base_url, self.day_path(day));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(map_ureq_error)?;
+
+        let html = response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        let message = extract_articles(&html)
+            .iter()
+            .map(|article| html_to_markdown(article, &self.base_url))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Ok(Verdict::from_response(&message))
+    }
+
+    fn day_path(&self, day: Day) -> String {
+        format!("/{}/day/{}", self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        match self.get_raw(path)? {
+            (200, body) => Ok(body),
+            (status, _) => Err(AocClientError::BadStatus(status)),
+        }
+    }
+
+    /// Returns status and body, including the body of error responses.
+    fn get_raw(&self, path: &str) -> Result<(u16, String), AocClientError> {
+        let response = match self
+            .agent
+            .get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &self.cookie())
+            .call()
+        {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(t)) => return Err(AocClientError::Transport(t.to_string())),
+        };
+
+        let status = response.status();
+        let body = response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string()))?;
+
+        Ok((status, body))
+    }
+}
+
+fn map_ureq_error(e: ureq::Error) -> AocClientError {
+    match e {
+        ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+        ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+    }
+}
+
+pub fn check() -> Result<(), AocClientError> {
+    session::load().ok_or(AocClientError::SessionNotFound)?;
+    get_year().ok_or(AocClientError::YearNotSet)?;
+    Ok(())
+}
+
+/// Fetches the puzzle description and stores it in the puzzle cache.
+pub fn read(day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.get_puzzle(day)?;
+    write_file(&get_puzzle_path(client.year(), day), &puzzle)?;
+    Ok(puzzle)
+}
+
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let input_path = get_input_path(client.year(), day);
+    let puzzle_path = get_puzzle_path(client.year(), day);
+
+    // fetch and validate everything before touching existing files.
+    let fetched = client
+        .get_input(day)
+        .and_then(|input| Ok((input, client.get_puzzle(day)?)));
+
+    let (input, puzzle) = match fetched {
+        Ok(fetched) => fetched,
+        Err(e) => {
+            if Path::new(&input_path).exists() {
+                eprintln!("Kept the previous contents of \"{input_path}\".");
+            }
+            return Err(e);
+        }
+    };
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetches a private leaderboard and stores it in the leaderboard cache.
+pub fn leaderboard(id: &str) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let json = client.get_leaderboard(id)?;
+    write_file(&get_leaderboard_path(client.year()), &json)?;
+    Ok(json)
+}
+
+pub fn submit(year: u16, day: Day, part: u8, result: &str) -> Result<Verdict, AocClientError> {
+    AocClient::from_env()?
+        .with_year(year)
+        .submit(day, part, result)
+}
+
+/// Detects error pages and empty bodies that are returned instead of an input.
+fn validate_input(body: &str) -> Result<(), InvalidInput> {
+    let trimmed = body.trim_start();
+
+    if trimmed.is_empty() {
+        Err(InvalidInput::Empty)
+    } else if trimmed.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        Err(InvalidInput::NotUnlocked)
+    } else if trimmed.contains("Puzzle inputs differ by user") {
+        Err(InvalidInput::LoggedOut)
+    } else if trimmed.starts_with("<!DOCTYPE")
+        || trimmed.starts_with("<html")
+        || trimmed.contains("<body")
+    {
+        Err(InvalidInput::Html)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn get_input_path(year: u16, day: Day) -> String {
+    format!("{}/inputs/{day}.txt", get_data_dir(year).display())
+}
+
+pub fn get_puzzle_path(year: u16, day: Day) -> String {
+    format!("{}/puzzles/{day}.md", get_data_dir(year).display())
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(|_| AocClientError::IoError)?;
+    }
+    fs::write(path, contents).map_err(|_| AocClientError::IoError)
+}
+
+pub fn get_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok().or(None),
+        Err(_) => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<(/?)([a-zA-Z0-9]+)([^>]*)>").unwrap();
+    static ref HREF: Regex = Regex::new(r#"href="([^"]*)""#).unwrap();
+    static ref BLANK_LINES: Regex = Regex::new(r"\n{3,}").unwrap();
+    static ref USER: Regex = Regex::new(r#"<div class="user">([^<]+)"#).unwrap();
+}
+
+fn extract_articles(html: &str) -> Vec<&str> {
+    ARTICLE
+        .captures_iter(html)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .collect()
+}
+
+/// Converts the subset of HTML used on puzzle pages to markdown.
+fn html_to_markdown(html: &str, base_url: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut last = 0;
+
+    for cap in TAG.captures_iter(html) {
+        let tag = cap.get(0).unwrap();
+        push_text(&mut out, &html[last..tag.start()], in_pre);
+        last = tag.end();
+
+        let closing = &cap[1] == "/";
+        match (cap[2].to_lowercase().as_str(), closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                let href = HREF
+                    .captures(&cap[3])
+                    .map(|c| c[1].to_string())
+                    .unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{base_url}{href}")
+                } else {
+                    href
+                };
+                links.push(href);
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    push_text(&mut out, &html[last..], in_pre);
+
+    let out = BLANK_LINES.replace_all(&out, "\n\n");
+    format!("{}\n", out.trim())
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    if in_pre {
+        out.push_str(&decode_entities(text));
+        return;
+    }
+
+    // whitespace between block elements carries no meaning.
+    if text.trim().is_empty() && text.contains('\n') {
+        return;
+    }
+
+    out.push_str(&escape_markdown(&decode_entities(&text.replace('\n', " "))));
+}
+
+/// Escapes characters that would otherwise be read as markdown syntax.
+fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '*' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, validate_input, AocClient, AocClientError, InvalidInput};
+    use crate::day;
+    use crate::template::verdict::Verdict;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves a single canned response and hands back the raw request it received.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session_cookie() {
+        let (url, server) = serve_once("200 OK", "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+
+        let input = client.get_input(day!(1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc123"));
+    }
+
+    #[test]
+    fn rejects_locked_inputs() {
+        let (url, server) = serve_once(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n",
+        );
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+
+        let result = client.get_input(day!(25));
+        server.join().unwrap();
+
+        assert!(matches!(
+            result,
+            Err(AocClientError::InvalidInput(InvalidInput::NotUnlocked))
+        ));
+    }
+
+    #[test]
+    fn validates_input_shapes() {
+        assert_eq!(validate_input("1abc2\n"), Ok(()));
+        assert_eq!(validate_input(""), Err(InvalidInput::Empty));
+        assert_eq!(validate_input("\n  \n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate_input(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InvalidInput::LoggedOut)
+        );
+        assert_eq!(
+            validate_input("<!DOCTYPE html>\n<html lang=\"en-us\"><body>[Log In]</body></html>"),
+            Err(InvalidInput::Html)
+        );
+        assert_eq!(validate_input("<<<>>>\n"), Ok(()));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 7: Camel Cards ---</h2><p>Play <em>cards</em>.</p></article></main>",
+        );
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+
+        let puzzle = client.get_puzzle(day!(7)).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(puzzle, "## --- Day 7: Camel Cards ---\n\nPlay *cards*.\n");
+    }
+
+    #[test]
+    fn posts_answer_as_form() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        );
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+
+        let response = client.submit(day!(4), 2, "42").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(response, Verdict::TooLow);
+        assert!(request.starts_with("POST /2023/day/4/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn validates_session() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<header><div class=\"user\">Jane Doe <span class=\"star-count\">32*</span></div></header>",
+        );
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+        assert_eq!(client.validate_session().unwrap(), "Jane Doe");
+        assert!(server.join().unwrap().starts_with("GET /2023 HTTP/1.1"));
+
+        let (url, server) = serve_once(
+            "200 OK",
+            "<header><a href=\"/2023/auth/login\">[Log In]</a></header>",
+        );
+        let client = AocClient::new("expired", 2023).with_base_url(&url);
+        assert!(matches!(
+            client.validate_session(),
+            Err(AocClientError::SessionInvalid)
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn fetches_leaderboard_json() {
+        let (url, server) = serve_once("200 OK", "{\"event\":\"2023\",\"members\":{}}");
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+        assert!(client
+            .get_leaderboard("1234")
+            .unwrap()
+            .contains("\"event\""));
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /2023/leaderboard/private/view/1234.json HTTP/1.1"));
+
+        let (url, server) = serve_once("200 OK", "<html>Leaderboards</html>");
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+        assert!(matches!(
+            client.get_leaderboard("1234"),
+            Err(AocClientError::LeaderboardUnavailable)
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, server) = serve_once("404 Not Found", "not found");
+        let client = AocClient::new("abc123", 2023).with_base_url(&url);
+
+        let result = client.get_input(day!(25));
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::BadStatus(404))));
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = [
+            "<h2>--- Day 1: Trebuchet?! ---</h2>",
+            "<p>See <a href=\"/2023/day/1/input\">input</a>.</p>",
+            "<pre><code>1abc2",
+            "a1b2c3&lt;",
+            "</code></pre>",
+            "<ul>",
+            "<li>Sum is <code><em>142</em></code>.</li>",
+            "<li>Gears are <code>*</code> [sic]</li>",
+            "</ul>",
+        ]
+        .join("\n");
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "See [input](https://example.com/2023/day/1/input).",
+            "",
+            "```",
+            "1abc2",
+            "a1b2c3<",
+            "```",
+            "",
+            "- Sum is `*142*`.",
+            "- Gears are `\\*` \\[sic\\]",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(&html, "https://example.com"), expected);
+    }
+}
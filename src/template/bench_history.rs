@@ -0,0 +1,434 @@
+/// History of every benchmarked run, to compare timings across commits.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use crate::template::benchmark::{Statistic, Summary};
+use crate::Day;
+
+pub const BENCH_HISTORY_PATH: &str = "data/benchmarks.tsv";
+
+/// Fraction a part has to get slower by to be flagged as a regression.
+const REGRESSION_THRESHOLD: f64 = 0.1;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u16,
+    pub day: Day,
+    /// The part number, `0` for a separately timed parse step.
+    pub part: u8,
+    /// Unix timestamp (seconds).
+    pub timestamp: i64,
+    /// Short hash of the commit, with a `-dirty` suffix if there were uncommitted changes.
+    pub commit: String,
+    pub release: bool,
+    pub summary: Summary,
+}
+
+/// What to compare a run against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Baseline {
+    /// The most recent earlier run.
+    Previous,
+    /// The most recent run of a commit, matched by prefix.
+    Commit(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub commit: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regression: bool,
+}
+
+impl Comparison {
+    /// Relative change from the baseline, e.g. `0.25` for 25% slower.
+    pub fn change(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64().max(f64::EPSILON) - 1.0
+    }
+}
+
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Loads the history at `path`. A missing file is treated as an empty history.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn record(&mut self, entry: Entry) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let s = &entry.summary;
+        let nanos: Vec<String> = [s.mean, s.median, s.min, s.max, s.std_dev, s.p95]
+            .iter()
+            .map(|d| d.as_nanos().to_string())
+            .collect();
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            entry.year,
+            entry.day,
+            entry.part,
+            entry.timestamp,
+            entry.commit,
+            if entry.release { "release" } else { "debug" },
+            s.samples,
+            nanos.join("\t")
+        )?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// The latest entry of every part of `year` recorded at or after `since`.
+    pub fn latest_since(&self, year: u16, release: bool, since: i64) -> Vec<&Entry> {
+        let mut latest: Vec<&Entry> = vec![];
+
+        for entry in self
+            .entries
+            .iter()
+            .filter(|e| e.year == year && e.release == release && e.timestamp >= since)
+        {
+            latest.retain(|e| (e.day, e.part) != (entry.day, entry.part));
+            latest.push(entry);
+        }
+
+        latest.sort_by_key(|e| (e.day, e.part));
+        latest
+    }
+
+    /// The entry `current` is compared against, recorded before it.
+    pub fn baseline(&self, current: &Entry, baseline: &Baseline, before: i64) -> Option<&Entry> {
+        self.entries.iter().rev().find(|e| {
+            e.year == current.year
+                && e.day == current.day
+                && e.part == current.part
+                && e.release == current.release
+                && e.timestamp < before
+                && match baseline {
+                    Baseline::Previous => true,
+                    Baseline::Commit(commit) => e.commit.starts_with(commit.as_str()),
+                }
+        })
+    }
+
+    /// Compares every part of `year` benched since `started` with its baseline.
+    pub fn compare(
+        &self,
+        year: u16,
+        release: bool,
+        started: i64,
+        baseline: &Baseline,
+        statistic: Statistic,
+    ) -> Vec<Comparison> {
+        self.latest_since(year, release, started)
+            .into_iter()
+            .filter_map(|current| {
+                let previous = self.baseline(current, baseline, started)?;
+                Some(Comparison {
+                    day: current.day,
+                    part: current.part,
+                    commit: previous.commit.clone(),
+                    baseline: previous.summary.get(statistic),
+                    current: current.summary.get(statistic),
+                    regression: is_regression(&previous.summary, &current.summary, statistic),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The name of a part in tables, e.g. `1`, or `parse` for the parse step.
+pub fn part_name(part: u8) -> String {
+    if part == 0 {
+        "parse".into()
+    } else {
+        part.to_string()
+    }
+}
+
+/// A part regressed if it got slower by more than [`REGRESSION_THRESHOLD`]
+/// and by more than the combined standard deviation of both runs, so noisy parts are not flagged.
+pub fn is_regression(baseline: &Summary, current: &Summary, statistic: Statistic) -> bool {
+    let before = baseline.get(statistic).as_secs_f64();
+    let after = current.get(statistic).as_secs_f64();
+    let noise = if statistic == Statistic::StdDev {
+        0.0
+    } else {
+        (baseline.std_dev + current.std_dev).as_secs_f64()
+    };
+
+    after > before * (1.0 + REGRESSION_THRESHOLD) && after - before > noise
+}
+
+/// The checked out commit, or `-` outside of a git repository.
+/// Changes to the README and `data/` do not mark it dirty, since benchmark runs write to both.
+pub fn current_commit() -> String {
+    let Some(hash) = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    else {
+        return "-".into();
+    };
+
+    let dirty = Command::new("git")
+        .args([
+            "status",
+            "--porcelain",
+            "--untracked-files=no",
+            "--",
+            ".",
+            ":!README.md",
+            ":!data",
+        ])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    if dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    }
+}
+
+fn parse(contents: &str) -> Result<Vec<Entry>, Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parse_err = || Error::Parser(format!("malformed benchmark history line: {line}"));
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            let [year, day, part, timestamp, commit, profile, samples, mean, median, min, max, std_dev, p95] =
+                fields[..]
+            else {
+                return Err(parse_err());
+            };
+
+            let nanos = |s: &str| s.parse().map(Duration::from_nanos).map_err(|_| parse_err());
+
+            Ok(Entry {
+                year: year.parse().map_err(|_| parse_err())?,
+                day: day.parse().map_err(|_| parse_err())?,
+                part: part.parse().map_err(|_| parse_err())?,
+                timestamp: timestamp.parse().map_err(|_| parse_err())?,
+                commit: commit.to_string(),
+                release: match profile {
+                    "release" => true,
+                    "debug" => false,
+                    _ => return Err(parse_err()),
+                },
+                summary: Summary {
+                    samples: samples.parse().map_err(|_| parse_err())?,
+                    mean: nanos(mean)?,
+                    median: nanos(median)?,
+                    min: nanos(min)?,
+                    max: nanos(max)?,
+                    std_dev: nanos(std_dev)?,
+                    p95: nanos(p95)?,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_regression, parse, part_name, Baseline, Entry, History};
+    use crate::day;
+    use crate::template::benchmark::{Statistic, Summary};
+    use std::time::Duration;
+
+    fn summary(mean_micros: u64, std_dev_micros: u64) -> Summary {
+        let mean = Duration::from_micros(mean_micros);
+        Summary {
+            samples: 100,
+            mean,
+            median: mean,
+            min: mean,
+            max: mean,
+            std_dev: Duration::from_micros(std_dev_micros),
+            p95: mean,
+        }
+    }
+
+    fn get_mock_history() -> &'static str {
+        "2023\t01\t1\t100\tabc1234\trelease\t100\t1000\t1000\t900\t1500\t50\t1200\n\
+         2023\t01\t1\t200\tdef5678\trelease\t100\t2000\t2000\t1900\t2500\t50\t2200\n\
+         2023\t01\t1\t200\tdef5678\tdebug\t1\t9000\t9000\t9000\t9000\t0\t9000\n\
+         2023\t01\t2\t200\tdef5678\trelease\t100\t500\t500\t400\t900\t10\t600\n\
+         2023\t01\t1\t300\t123abcd-dirty\trelease\t100\t2050\t2050\t1900\t2500\t50\t2200\n\
+         2023\t01\t2\t300\t123abcd-dirty\trelease\t100\t800\t800\t700\t900\t10\t850\n"
+    }
+
+    #[test]
+    fn parses_history() {
+        let entries = parse(get_mock_history()).unwrap();
+        assert_eq!(entries.len(), 6);
+        assert_eq!(
+            entries[0],
+            Entry {
+                year: 2023,
+                day: day!(1),
+                part: 1,
+                timestamp: 100,
+                commit: "abc1234".into(),
+                release: true,
+                summary: Summary {
+                    samples: 100,
+                    mean: Duration::from_nanos(1000),
+                    median: Duration::from_nanos(1000),
+                    min: Duration::from_nanos(900),
+                    max: Duration::from_nanos(1500),
+                    std_dev: Duration::from_nanos(50),
+                    p95: Duration::from_nanos(1200),
+                },
+            }
+        );
+        assert!(!entries[2].release);
+        assert!(parse("2023\t01\t1\t100\n").is_err());
+    }
+
+    #[test]
+    fn records_and_reloads() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-bench-history-test-{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let entry = Entry {
+            year: 2023,
+            day: day!(3),
+            part: 2,
+            timestamp: 10,
+            commit: "abc1234".into(),
+            release: true,
+            summary: summary(12, 1),
+        };
+        History::load(&path).unwrap().record(entry.clone()).unwrap();
+
+        let reloaded = History::load(&path).unwrap();
+        assert_eq!(reloaded.latest_since(2023, true, 0), vec![&entry]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn compares_with_previous_run() {
+        let history = History {
+            path: "unused".into(),
+            entries: parse(get_mock_history()).unwrap(),
+        };
+
+        let comparisons = history.compare(2023, true, 300, &Baseline::Previous, Statistic::Mean);
+        assert_eq!(comparisons.len(), 2);
+        // 2000ns to 2050ns is within the threshold.
+        assert_eq!(comparisons[0].commit, "def5678");
+        assert!(!comparisons[0].regression);
+        // 500ns to 800ns is a regression.
+        assert_eq!(comparisons[1].current, Duration::from_nanos(800));
+        assert!(comparisons[1].regression);
+        assert!((comparisons[1].change() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compares_with_commit() {
+        let history = History {
+            path: "unused".into(),
+            entries: parse(get_mock_history()).unwrap(),
+        };
+
+        let comparisons = history.compare(
+            2023,
+            true,
+            300,
+            &Baseline::Commit("abc".into()),
+            Statistic::Mean,
+        );
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline, Duration::from_nanos(1000));
+        assert!(comparisons[0].regression);
+    }
+
+    #[test]
+    fn ignores_noisy_changes() {
+        assert!(is_regression(
+            &summary(100, 1),
+            &summary(120, 1),
+            Statistic::Mean
+        ));
+        assert!(!is_regression(
+            &summary(100, 1),
+            &summary(105, 1),
+            Statistic::Mean
+        ));
+        assert!(!is_regression(
+            &summary(100, 15),
+            &summary(120, 15),
+            Statistic::Mean
+        ));
+        assert!(!is_regression(
+            &summary(100, 1),
+            &summary(80, 1),
+            Statistic::Mean
+        ));
+    }
+
+    #[test]
+    fn compares_parse_steps() {
+        let history = History {
+            path: "unused".into(),
+            entries: parse(
+                "2023\t04\t0\t100\tabc1234\trelease\t100\t1000\t1000\t900\t1500\t10\t1200\n\
+                 2023\t04\t0\t200\tdef5678\trelease\t100\t2000\t2000\t1900\t2500\t10\t2200\n",
+            )
+            .unwrap(),
+        };
+
+        let comparisons = history.compare(2023, true, 200, &Baseline::Previous, Statistic::Mean);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 0);
+        assert!(comparisons[0].regression);
+
+        assert_eq!(part_name(0), "parse");
+        assert_eq!(part_name(2), "2");
+    }
+}
@@ -0,0 +1,413 @@
+/// Summaries of benchmark samples.
+use std::{cmp, env, fmt::Display, str::FromStr, time::Duration};
+
+/// A statistic of the samples of a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Statistic {
+    #[default]
+    Mean,
+    Median,
+    Min,
+    Max,
+    StdDev,
+    P95,
+}
+
+impl Statistic {
+    pub const ALL: [Statistic; 6] = [
+        Statistic::Mean,
+        Statistic::Median,
+        Statistic::Min,
+        Statistic::Max,
+        Statistic::StdDev,
+        Statistic::P95,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Statistic::Mean => "mean",
+            Statistic::Median => "median",
+            Statistic::Min => "min",
+            Statistic::Max => "max",
+            Statistic::StdDev => "stddev",
+            Statistic::P95 => "p95",
+        }
+    }
+}
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Statistic::ALL
+            .into_iter()
+            .find(|statistic| statistic.key() == s)
+            .ok_or_else(|| {
+                let keys: Vec<&str> = Statistic::ALL.iter().map(|s| s.key()).collect();
+                format!(
+                    "unknown statistic \"{s}\", expected one of {}",
+                    keys.join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub samples: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// The 95th percentile, by nearest rank.
+    pub p95: Duration,
+}
+
+impl Summary {
+    /// Summarizes `samples`. [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        let p95_rank = (n * 95).div_ceil(100);
+
+        Some(Self {
+            samples: n,
+            mean: Duration::from_nanos(mean.round() as u64),
+            median,
+            min: sorted[0],
+            max: sorted[n - 1],
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            p95: sorted[p95_rank - 1],
+        })
+    }
+
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+            Statistic::Min => self.min,
+            Statistic::Max => self.max,
+            Statistic::StdDev => self.std_dev,
+            Statistic::P95 => self.p95,
+        }
+    }
+
+    /// Every statistic except the mean, e.g. `median 70.2µs, min 60.1µs, max 120.3µs, stddev 5.0µs, p95 90.1µs`.
+    pub fn format_spread(&self) -> String {
+        Statistic::ALL
+            .into_iter()
+            .filter(|statistic| *statistic != Statistic::Mean)
+            .map(|statistic| format!("{statistic} {:.1?}", self.get(statistic)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// How long solutions are benched for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs before sampling starts.
+    pub warmup: u32,
+    /// Time budget the number of samples is derived from.
+    pub target_time: Duration,
+    pub min_samples: u32,
+    pub max_samples: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 0,
+            target_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// The defaults, overridden by the `AOC_BENCH_*` environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let overrides = BenchOverrides {
+            warmup: var("AOC_BENCH_WARMUP")
+                .map(|v| parse_count(&v))
+                .transpose()?,
+            target_time: var("AOC_BENCH_TIME").map(|v| parse_time(&v)).transpose()?,
+            min_samples: var("AOC_BENCH_MIN_SAMPLES")
+                .map(|v| parse_count(&v))
+                .transpose()?,
+            max_samples: var("AOC_BENCH_MAX_SAMPLES")
+                .map(|v| parse_count(&v))
+                .transpose()?,
+        };
+
+        Self::default().apply(&overrides)
+    }
+
+    pub fn apply(&self, overrides: &BenchOverrides) -> Result<Self, String> {
+        let config = Self {
+            warmup: overrides.warmup.unwrap_or(self.warmup),
+            target_time: overrides.target_time.unwrap_or(self.target_time),
+            min_samples: overrides.min_samples.unwrap_or(self.min_samples),
+            max_samples: overrides.max_samples.unwrap_or(self.max_samples),
+        };
+
+        if config.min_samples == 0 || config.min_samples > config.max_samples {
+            return Err(format!(
+                "invalid sample limits: min {} and max {}",
+                config.min_samples, config.max_samples
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// The number of samples that fit the target time if one run takes `base_time`.
+    pub fn samples(&self, base_time: Duration) -> u32 {
+        let samples = self.target_time.as_nanos() / cmp::max(base_time.as_nanos(), 10);
+        u32::try_from(samples)
+            .unwrap_or(u32::MAX)
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+/// Benchmark settings passed on the command line, which take precedence over [`BenchConfig::from_env`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchOverrides {
+    pub warmup: Option<u32>,
+    pub target_time: Option<Duration>,
+    pub min_samples: Option<u32>,
+    pub max_samples: Option<u32>,
+}
+
+impl BenchOverrides {
+    /// Reads `--warmup`, `--bench-time`, `--min-samples` and `--max-samples` from `args`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| {
+            let index = args.iter().position(|arg| arg == flag)?;
+            Some(
+                args.get(index + 1)
+                    .map(String::as_str)
+                    .ok_or_else(|| format!("missing value for {flag}")),
+            )
+        };
+
+        Ok(Self {
+            warmup: value("--warmup").map(|v| parse_count(v?)).transpose()?,
+            target_time: value("--bench-time").map(|v| parse_time(v?)).transpose()?,
+            min_samples: value("--min-samples")
+                .map(|v| parse_count(v?))
+                .transpose()?,
+            max_samples: value("--max-samples")
+                .map(|v| parse_count(v?))
+                .transpose()?,
+        })
+    }
+
+    /// The flags to forward the overrides to a solution.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        let mut push = |flag: &str, value: Option<String>| {
+            if let Some(value) = value {
+                args.push(flag.to_string());
+                args.push(value);
+            }
+        };
+
+        push("--warmup", self.warmup.map(|v| v.to_string()));
+        push(
+            "--bench-time",
+            // full precision, so sub-millisecond budgets are not rounded down to zero.
+            self.target_time.map(|t| format!("{}s", t.as_secs_f64())),
+        );
+        push("--min-samples", self.min_samples.map(|v| v.to_string()));
+        push("--max-samples", self.max_samples.map(|v| v.to_string()));
+
+        args
+    }
+}
+
+pub fn parse_count(s: &str) -> Result<u32, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("\"{s}\" is not a number"))
+}
+
+/// Parses a time like `500ms`, `2s` or `1.5s`. Plain numbers are seconds.
+pub fn parse_time(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (value, millis) = match s.strip_suffix("ms") {
+        Some(value) => (value, true),
+        None => (s.strip_suffix('s').unwrap_or(s), false),
+    };
+
+    let value: f64 = value
+        .parse()
+        .ok()
+        .filter(|value: &f64| value.is_finite() && *value > 0.0)
+        .ok_or_else(|| format!("\"{s}\" is not a time like 500ms or 2s"))?;
+
+    Ok(if millis {
+        Duration::from_secs_f64(value / 1000.0)
+    } else {
+        Duration::from_secs_f64(value)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_time, BenchConfig, BenchOverrides, Statistic, Summary};
+    use std::time::Duration;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let summary = Summary::from_samples(&micros(&[4, 2, 9, 5, 4, 5, 7, 4])).unwrap();
+
+        assert_eq!(summary.samples, 8);
+        assert_eq!(summary.mean, Duration::from_micros(5));
+        assert_eq!(
+            summary.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(summary.min, Duration::from_micros(2));
+        assert_eq!(summary.max, Duration::from_micros(9));
+        assert_eq!(summary.std_dev, Duration::from_micros(2));
+        assert_eq!(summary.p95, Duration::from_micros(9));
+
+        assert!(Summary::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn summarizes_single_sample() {
+        let summary = Summary::from_samples(&micros(&[3])).unwrap();
+        for statistic in Statistic::ALL {
+            let expected = match statistic {
+                Statistic::StdDev => Duration::ZERO,
+                _ => Duration::from_micros(3),
+            };
+            assert_eq!(summary.get(statistic), expected);
+        }
+    }
+
+    #[test]
+    fn formats_spread() {
+        let summary = Summary::from_samples(&micros(&[1, 3])).unwrap();
+        assert_eq!(
+            summary.format_spread(),
+            "median 2.0µs, min 1.0µs, max 3.0µs, stddev 1.0µs, p95 3.0µs"
+        );
+    }
+
+    #[test]
+    fn parses_statistics() {
+        for statistic in Statistic::ALL {
+            assert_eq!(statistic.to_string().parse(), Ok(statistic));
+        }
+        assert!("average".parse::<Statistic>().is_err());
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_time("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_time("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_time("1.5"), Ok(Duration::from_millis(1500)));
+        assert!(parse_time("0s").is_err());
+        assert!(parse_time("soon").is_err());
+    }
+
+    #[test]
+    fn reads_and_forwards_overrides() {
+        let args: Vec<String> = [
+            "--time",
+            "--warmup",
+            "3",
+            "--bench-time",
+            "2s",
+            "--max-samples",
+            "50",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        let overrides = BenchOverrides::from_args(&args).unwrap();
+        assert_eq!(
+            overrides,
+            BenchOverrides {
+                warmup: Some(3),
+                target_time: Some(Duration::from_secs(2)),
+                min_samples: None,
+                max_samples: Some(50),
+            }
+        );
+        assert_eq!(
+            BenchOverrides::from_args(&overrides.to_args()),
+            Ok(overrides)
+        );
+
+        for millis in [0.5, 1.5, 250.0] {
+            let overrides = BenchOverrides {
+                target_time: Some(Duration::from_secs_f64(millis / 1000.0)),
+                ..Default::default()
+            };
+            assert_eq!(
+                BenchOverrides::from_args(&overrides.to_args()),
+                Ok(overrides)
+            );
+        }
+
+        let missing = vec!["--warmup".to_string()];
+        assert!(BenchOverrides::from_args(&missing).is_err());
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let config = BenchConfig::default()
+            .apply(&BenchOverrides {
+                target_time: Some(Duration::from_millis(100)),
+                max_samples: Some(500),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(config.warmup, 0);
+        assert_eq!(config.min_samples, 10);
+        assert_eq!(config.samples(Duration::from_micros(1)), 500);
+        assert_eq!(config.samples(Duration::from_millis(2)), 50);
+        assert_eq!(config.samples(Duration::from_secs(1)), 10);
+
+        let invalid = BenchOverrides {
+            min_samples: Some(100),
+            max_samples: Some(10),
+            ..Default::default()
+        };
+        assert!(BenchConfig::default().apply(&invalid).is_err());
+    }
+}
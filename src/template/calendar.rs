@@ -0,0 +1,133 @@
+/// ASCII advent calendar with the progress of every day.
+use crate::template::{markdown::RenderMode, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDay {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub stars: usize,
+}
+
+/// Renders the days in rows of five. `today` is highlighted, in plain mode by arrows around the day number.
+pub fn render(days: &[CalendarDay], today: Option<Day>, mode: RenderMode) -> String {
+    let border = format!(
+        "+{}\n",
+        format!("{}+", "-".repeat(CELL_WIDTH)).repeat(COLUMNS)
+    );
+    let mut out = border.clone();
+
+    for row in days.chunks(COLUMNS) {
+        let numbers: Vec<String> = row
+            .iter()
+            .map(|d| {
+                let is_today = today == Some(d.day);
+                let number = match (is_today, mode) {
+                    (true, RenderMode::Plain) => format!(">{}<", d.day),
+                    _ => d.day.to_string(),
+                };
+                let cell = center(&number);
+                match (is_today, mode) {
+                    (true, RenderMode::Ansi) => format!("{ANSI_BOLD}{cell}{ANSI_RESET}"),
+                    _ => cell,
+                }
+            })
+            .collect();
+
+        let progress: Vec<String> = row
+            .iter()
+            .map(|d| {
+                let cell = center(&progress(d));
+                match (d.stars, mode) {
+                    (0, _) | (_, RenderMode::Plain) => cell,
+                    (1, RenderMode::Ansi) => format!("{ANSI_ITALIC}{cell}{ANSI_RESET}"),
+                    (_, RenderMode::Ansi) => format!("{ANSI_BOLD}{cell}{ANSI_RESET}"),
+                }
+            })
+            .collect();
+
+        // the last row of a year with fewer puzzles is padded with empty cells.
+        let padding = vec![center(""); COLUMNS - row.len()];
+        let numbers = [numbers, padding.clone()].concat();
+        let progress = [progress, padding].concat();
+
+        out.push_str(&format!("|{}|\n", numbers.join("|")));
+        out.push_str(&format!("|{}|\n", progress.join("|")));
+        out.push_str(&border);
+    }
+
+    let total: usize = days.iter().map(|d| d.stars).sum();
+    out.push_str(&format!(
+        "{total}/{} stars   ** solved   * part one   ~ started\n",
+        days.len() * 2
+    ));
+
+    out
+}
+
+fn progress(day: &CalendarDay) -> String {
+    match day.stars {
+        0 if day.scaffolded => "~".into(),
+        stars => "*".repeat(stars),
+    }
+}
+
+fn center(s: &str) -> String {
+    format!("{s:^CELL_WIDTH$}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, CalendarDay};
+    use crate::template::markdown::RenderMode;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use crate::{all_days_of, day};
+
+    fn get_mock_days(year: u16) -> Vec<CalendarDay> {
+        all_days_of(year)
+            .map(|day| CalendarDay {
+                day,
+                scaffolded: day <= 3,
+                stars: match day.into_inner() {
+                    1 => 2,
+                    2 => 1,
+                    _ => 0,
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_plain_calendar() {
+        let rendered = render(&get_mock_days(2023), Some(day!(3)), RenderMode::Plain);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 17);
+        assert_eq!(lines[0], "+--------+--------+--------+--------+--------+");
+        assert_eq!(lines[1], "|   01   |   02   |  >03<  |   04   |   05   |");
+        assert_eq!(lines[2], "|   **   |   *    |   ~    |        |        |");
+        assert_eq!(lines[4], "|   06   |   07   |   08   |   09   |   10   |");
+        assert_eq!(lines[16], "3/50 stars   ** solved   * part one   ~ started");
+    }
+
+    #[test]
+    fn pads_short_last_row() {
+        let rendered = render(&get_mock_days(2025), None, RenderMode::Plain);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[7], "|   11   |   12   |        |        |        |");
+        assert_eq!(lines[9], "+--------+--------+--------+--------+--------+");
+        assert_eq!(lines[10], "3/24 stars   ** solved   * part one   ~ started");
+    }
+
+    #[test]
+    fn highlights_today() {
+        let rendered = render(&get_mock_days(2023), Some(day!(3)), RenderMode::Ansi);
+        assert!(rendered.contains(&format!("{ANSI_BOLD}   03   {ANSI_RESET}")));
+        assert!(!rendered.contains(">03<"));
+    }
+}
@@ -0,0 +1,323 @@
+/// All solutions live in isolated binaries.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+use std::{
+    io::{self, BufRead, BufReader},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+};
+
+use crate::template::{
+    benchmark::{BenchOverrides, Statistic},
+    commands::all::{get_bin_name, get_path_for_bin},
+    readme_benchmarks::Timings,
+};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    BrokenPipe,
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Run the solution bin for a given day of the checkout in `dir`.
+pub fn run_solution(
+    dir: &Path,
+    year: u16,
+    day: Day,
+    is_timed: bool,
+    bench: &BenchOverrides,
+    is_release: bool,
+) -> Result<Vec<String>, Error> {
+    // skip command invocation for days that have not been scaffolded yet.
+    if !dir.join(get_path_for_bin(year, day)).exists() {
+        return Ok(vec![]);
+    }
+
+    let bin_name = get_bin_name(year, day);
+    let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    let bench_args = bench.to_args();
+    if is_timed {
+        // mirror `--time` and the bench flags to child invocations.
+        args.push("--");
+        args.push("--time");
+        args.extend(bench_args.iter().map(String::as_str));
+    }
+
+    // spawn child command with piped stdout/stderr.
+    // forward output to stdout/stderr while grabbing stdout lines.
+
+    let mut cmd = Command::new("cargo")
+        .current_dir(dir)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+    let stderr = BufReader::new(cmd.stderr.take().ok_or(Error::BrokenPipe)?);
+
+    let mut output = vec![];
+
+    let thread = thread::spawn(move || {
+        stderr.lines().for_each(|line| {
+            eprintln!("{}", line.unwrap());
+        });
+    });
+
+    for line in stdout.lines() {
+        let line = line.unwrap();
+        println!("{line}");
+        output.push(line);
+    }
+
+    thread.join().unwrap();
+    cmd.wait()?;
+
+    Ok(output)
+}
+
+/// The time of a part as printed by its solution.
+pub struct PartTime<'a> {
+    /// The part number, `0` for a separately timed parse step.
+    pub part: Option<u8>,
+    /// The formatted time of the selected statistic.
+    pub time: &'a str,
+    /// The time of the selected statistic in nanoseconds.
+    pub nanos: f64,
+    /// The mean time in nanoseconds, which adds up to the total run time.
+    pub mean_nanos: f64,
+}
+
+/// Reads the timings of both parts. `statistic` selects which of the reported statistics is shown,
+/// the total is always the sum of the means.
+pub fn parse_exec_time(output: &[String], day: Day, statistic: Statistic) -> Timings {
+    let mut timings = Timings {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+    };
+
+    for time in parse_part_times(output, statistic) {
+        match time.part {
+            Some(0) => timings.parse = Some(time.time.into()),
+            Some(1) => timings.part_1 = Some(time.time.into()),
+            Some(2) => timings.part_2 = Some(time.time.into()),
+            _ => {}
+        }
+
+        timings.total_nanos += time.mean_nanos;
+    }
+
+    timings
+}
+
+/// Reads the time of every timed part.
+pub fn parse_part_times(output: &[String], statistic: Statistic) -> Vec<PartTime<'_>> {
+    let mut times = vec![];
+    let mut lines = output.iter().peekable();
+
+    while let Some(l) = lines.next() {
+        if !l.contains(" samples)") {
+            continue;
+        }
+
+        let Some((mean_str, mean_nanos)) = parse_time(l) else {
+            eprintln!("Could not parse timings from line: {l}");
+            continue;
+        };
+
+        // the spread of the samples is reported on the following line.
+        let (time, nanos) = match lines.peek().and_then(|next| parse_spread(next, statistic)) {
+            Some(spread) => {
+                lines.next();
+                spread
+            }
+            None => (mean_str, mean_nanos),
+        };
+
+        let part = l.split(':').next().unwrap_or_default();
+        let part = if part.contains("Parse") {
+            Some(0)
+        } else if part.contains("Part 1") {
+            Some(1)
+        } else if part.contains("Part 2") {
+            Some(2)
+        } else {
+            None
+        };
+
+        times.push(PartTime {
+            part,
+            time,
+            nanos,
+            mean_nanos,
+        });
+    }
+
+    times
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+fn parse_time(line: &str) -> Option<(&str, f64)> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let str_timing = line
+        .split(" samples)")
+        .next()?
+        .split('(')
+        .next_back()?
+        .split('@')
+        .next()?
+        .trim();
+
+    Some((str_timing, parse_duration(str_timing)?))
+}
+
+/// Reads `statistic` from a line like `median 70.2µs, min 60.1µs, max 120.3µs, stddev 5.0µs, p95 90.1µs`.
+fn parse_spread(line: &str, statistic: Statistic) -> Option<(&str, f64)> {
+    line.trim().split(", ").find_map(|entry| {
+        let (name, value) = entry.split_once(' ')?;
+        if name != statistic.key() {
+            return None;
+        }
+        Some((value, parse_duration(value)?))
+    })
+}
+
+fn parse_duration(s: &str) -> Option<f64> {
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_exec_time;
+    use crate::template::benchmark::Statistic;
+
+    use crate::day;
+
+    #[test]
+    fn test_well_formed() {
+        let res = parse_exec_time(
+            &[
+                "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                "".into(),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+        assert_eq!(res.part_1.unwrap(), "74.13ns");
+        assert_eq!(res.part_2.unwrap(), "74.13ms");
+    }
+
+    #[test]
+    fn test_patterns_in_input() {
+        let res = parse_exec_time(
+            &[
+                "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
+                "Part 2: 10s (100ms @ 1 samples)".into(),
+                "".into(),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_approx_eq!(res.total_nanos, 2100000000_f64);
+        assert_eq!(res.part_1.unwrap(), "2s");
+        assert_eq!(res.part_2.unwrap(), "100ms");
+    }
+
+    #[test]
+    fn test_missing_parts() {
+        let res = parse_exec_time(
+            &[
+                "Part 1: ✖        ".into(),
+                "Part 2: ✖        ".into(),
+                "".into(),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    #[test]
+    fn test_parse_step() {
+        let res = parse_exec_time(
+            &[
+                "Parse: (1.5ms @ 200 samples)".into(),
+                "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                "Part 2: 10 (2.0ms @ 500 samples)".into(),
+                "".into(),
+            ],
+            day!(1),
+            Statistic::Mean,
+        );
+        assert_approx_eq!(res.total_nanos, 3500074.13_f64);
+        assert_eq!(res.parse.unwrap(), "1.5ms");
+        assert_eq!(res.part_1.unwrap(), "74.13ns");
+    }
+
+    #[test]
+    fn test_statistics() {
+        let output = [
+            "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+            "    median 70.0ns, min 60.0ns, max 1.2µs, stddev 5.0ns, p95 90.0ns".into(),
+            "Part 2: 10 (2.0ms @ 500 samples)".into(),
+            "    median 1.5ms, min 1.0ms, max 9.0ms, stddev 1.1ms, p95 3.0ms".into(),
+            "".into(),
+        ];
+
+        // the total is the sum of the means, whichever statistic is shown.
+        let res = parse_exec_time(&output, day!(1), Statistic::Median);
+        assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+        assert_eq!(res.part_1.unwrap(), "70.0ns");
+        assert_eq!(res.part_2.unwrap(), "1.5ms");
+
+        let res = parse_exec_time(&output, day!(1), Statistic::Max);
+        assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+        assert_eq!(res.part_1.unwrap(), "1.2µs");
+
+        let res = parse_exec_time(&output, day!(1), Statistic::Mean);
+        assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+        assert_eq!(res.part_2.unwrap(), "2.0ms");
+    }
+}
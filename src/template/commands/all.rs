@@ -1,28 +1,61 @@
-use std::io;
+use std::{path::Path, process};
+
+use chrono::Utc;
 
 use crate::template::{
+    aoc_client::{self, AocClientError},
+    bench_history::{part_name, Baseline, History, BENCH_HISTORY_PATH},
+    benchmark::{BenchOverrides, Statistic},
+    child_commands,
     readme_benchmarks::{self, Timings},
+    titles::Titles,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days_of, Day};
+
+/// Runs every scaffolded solution. `bench` is forwarded to every solution if `is_timed` is set.
+/// `with_titles` adds puzzle titles to the README benchmark table, `statistic` selects which statistic of the samples it shows.
+/// If a `baseline` is given, the timings are compared with it and the command fails if a part regressed.
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    bench: &BenchOverrides,
+    with_titles: bool,
+    statistic: Statistic,
+    baseline: Option<Baseline>,
+) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    if baseline.is_some() && !is_timed {
+        eprintln!("Comparing with a baseline requires --time.");
+        process::exit(1);
+    }
+
+    let started = Utc::now().timestamp();
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let titles = Titles::load(year);
 
-    all_days().for_each(|day| {
+    all_days_of(year).for_each(|day| {
         if day > 1 {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        let heading = titles.heading(day);
+        println!("{ANSI_BOLD}{heading}{ANSI_RESET}");
+        println!("{}", "-".repeat(heading.chars().count()));
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output =
+            child_commands::run_solution(Path::new("."), year, day, is_timed, bench, is_release)
+                .unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let val = child_commands::parse_exec_time(&output, day, statistic);
             timings.push(val);
         }
     });
@@ -33,222 +66,85 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(
+                year,
+                timings,
+                total_millis,
+                with_titles.then_some(&titles),
+                statistic,
+            ) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
-    }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+        if let Some(baseline) = baseline {
+            if !compare(year, is_release, started, &baseline, statistic) {
+                process::exit(1);
+            }
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+/// Prints the change of every part since the baseline. Returns `false` if a part regressed.
+fn compare(
+    year: u16,
+    is_release: bool,
+    started: i64,
+    baseline: &Baseline,
+    statistic: Statistic,
+) -> bool {
+    let history = History::load(BENCH_HISTORY_PATH).unwrap_or_else(|e| {
+        eprintln!("failed to load benchmark history: {e:?}");
+        process::exit(1);
+    });
 
-        let mut output = vec![];
+    let comparisons = history.compare(year, is_release, started, baseline, statistic);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    println!("\n{ANSI_BOLD}Comparison of the {statistic} with the baseline:{ANSI_RESET}");
+    if comparisons.is_empty() {
+        println!("No earlier benchmarks to compare with.");
+        return true;
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
+    println!(
+        "{ANSI_BOLD}{:<5}{:<6}{:<15}{:>12}{:>12}{:>9}{ANSI_RESET}",
+        "Day", "Part", "Commit", "Before", "After", "Change"
+    );
+    for c in &comparisons {
+        let line = format!(
+            "{:<5}{:<6}{:<15}{:>12}{:>12}{:>+8.1}%",
+            c.day.to_string(),
+            part_name(c.part),
+            c.commit,
+            format!("{:.1?}", c.baseline),
+            format!("{:.1?}", c.current),
+            c.change() * 100.0,
+        );
+        if c.regression {
+            println!("{ANSI_BOLD}{line}  ▲ slower{ANSI_RESET}");
+        } else {
             println!("{line}");
-            output.push(line);
         }
-
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
+    let regressions = comparisons.iter().filter(|c| c.regression).count();
+    if regressions > 0 {
+        let parts = if regressions == 1 { "part" } else { "parts" };
+        println!("\n{regressions} {parts} got significantly slower.");
     }
+    regressions == 0
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+/// Solutions are addressed by year and day, e.g. `2023-07`.
+#[must_use]
+pub fn get_bin_name(year: u16, day: Day) -> String {
+    format!("{year}-{day}")
+}
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
-    }
+#[must_use]
+pub fn get_path_for_bin(year: u16, day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(year, day))
 }
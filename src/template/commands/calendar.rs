@@ -0,0 +1,58 @@
+use std::{env, fs, path::Path, process};
+
+use crate::all_days_of;
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    calendar::{self, CalendarDay},
+    commands::all::get_path_for_bin,
+    markdown::RenderMode,
+    readme_stars,
+    submissions::{Ledger, LEDGER_PATH},
+    unlock::{self, Clock},
+};
+
+/// Prints the calendar. Stars are taken from the README table or the submission ledger, whichever has more.
+pub fn handle(plain: bool, clock: &impl Clock) {
+    let mode = if plain || env::var_os("NO_COLOR").is_some() {
+        RenderMode::Plain
+    } else {
+        RenderMode::Ansi
+    };
+
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let readme_stars = fs::read_to_string("README.md")
+        .ok()
+        .and_then(|readme| readme_stars::read(&readme, year).ok())
+        .unwrap_or_default();
+    let ledger = Ledger::load(LEDGER_PATH).unwrap_or_else(|e| {
+        eprintln!("failed to load submission ledger: {e:?}");
+        process::exit(1);
+    });
+
+    let days: Vec<CalendarDay> = all_days_of(year)
+        .map(|day| {
+            let listed = readme_stars
+                .iter()
+                .find(|(d, _)| *d == day)
+                .map_or(0, |(_, stars)| *stars);
+            let recorded = (1..=2)
+                .filter(|part| ledger.is_solved(year, day, *part))
+                .count();
+
+            CalendarDay {
+                day,
+                scaffolded: Path::new(&get_path_for_bin(year, day)).exists(),
+                stars: listed.max(recorded),
+            }
+        })
+        .collect();
+
+    print!(
+        "{}",
+        calendar::render(&days, unlock::today(year, clock), mode)
+    );
+}
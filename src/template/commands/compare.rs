@@ -0,0 +1,251 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    time::Duration,
+};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    bench_history::part_name,
+    benchmark::{BenchOverrides, Statistic},
+    child_commands,
+    commands::all::get_path_for_bin,
+    get_data_dir, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{all_days_of, Day};
+
+/// Times of every part of a revision in nanoseconds.
+type PartTimes = BTreeMap<(Day, u8), f64>;
+
+/// Benchmarks `days` (all if empty) of two revisions on the inputs of the working tree and prints their speedup.
+pub fn handle(
+    rev_a: &str,
+    rev_b: &str,
+    days: Vec<Day>,
+    bench: &BenchOverrides,
+    statistic: Statistic,
+) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let days = if days.is_empty() {
+        all_days_of(year).collect()
+    } else {
+        days
+    };
+
+    // both revisions share a target directory, so dependencies are only built once.
+    let target_dir =
+        env::var_os("CARGO_TARGET_DIR").map_or_else(|| PathBuf::from("target"), PathBuf::from);
+    let target_dir = env::current_dir()
+        .map(|cwd| cwd.join(target_dir).join("compare"))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to read the working directory: {e}");
+            process::exit(1);
+        });
+    env::set_var("CARGO_TARGET_DIR", target_dir);
+
+    let mut results = vec![];
+    for rev in [rev_a, rev_b] {
+        match run_revision(year, rev, &days, bench, statistic) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("Failed to benchmark {rev}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let [(name_a, times_a), (name_b, times_b)] = &results[..] else {
+        unreachable!("two revisions were benchmarked");
+    };
+    print_table(name_a, times_a, name_b, times_b, statistic);
+}
+
+/// A git worktree in a temporary directory, removed when dropped.
+struct Worktree {
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(rev: &str) -> Result<Self, String> {
+        let name: String = rev
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let path = env::temp_dir().join(format!("aoc-compare-{}-{name}", process::id()));
+
+        let output = Command::new("git")
+            .args(["worktree", "add", "--detach"])
+            .arg(&path)
+            .arg(rev)
+            .output()
+            .map_err(|e| format!("failed to run git: {e}"))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let removed = Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .output()
+            .is_ok_and(|output| output.status.success());
+
+        if !removed {
+            eprintln!(
+                "Failed to remove the worktree at \"{}\". Clean it up with `git worktree prune`.",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Checks out `rev`, runs the timed solutions of `days` and returns its short commit hash with the times.
+fn run_revision(
+    year: u16,
+    rev: &str,
+    days: &[Day],
+    bench: &BenchOverrides,
+    statistic: Statistic,
+) -> Result<(String, PartTimes), String> {
+    let name = short_hash(rev)?;
+    let worktree = Worktree::add(rev)?;
+
+    // revisions from before the current layout, e.g. without a directory per year, cannot be run.
+    if !days
+        .iter()
+        .any(|day| worktree.path.join(get_path_for_bin(year, *day)).exists())
+    {
+        return Err(format!(
+            "it has no solutions for {year} in the expected layout ({})",
+            get_path_for_bin(year, days[0])
+        ));
+    }
+
+    copy_inputs(year, &worktree.path).map_err(|e| format!("failed to copy inputs: {e}"))?;
+
+    println!("{ANSI_BOLD}Benchmarking {rev} ({name}){ANSI_RESET}");
+
+    let mut times = PartTimes::new();
+    for day in days {
+        let output = child_commands::run_solution(&worktree.path, year, *day, true, bench, true)
+            .map_err(|e| format!("failed to run day {day}: {e:?}"))?;
+
+        for time in child_commands::parse_part_times(&output, statistic) {
+            if let Some(part) = time.part {
+                times.insert((*day, part), time.nanos);
+            }
+        }
+    }
+    println!();
+
+    if times.is_empty() {
+        return Err(format!("its solutions reported no timings for {year}"));
+    }
+
+    Ok((name, times))
+}
+
+fn short_hash(rev: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "--verify"])
+        .arg(format!("{rev}^{{commit}}"))
+        .output()
+        .map_err(|e| format!("failed to run git: {e}"))?;
+
+    if !output.status.success() {
+        return Err(format!("unknown revision \"{rev}\""));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Inputs are not committed, so the worktree gets a copy of the inputs of the working tree.
+fn copy_inputs(year: u16, worktree: &Path) -> std::io::Result<()> {
+    let inputs = get_data_dir(year).join("inputs");
+    let target = worktree.join(&inputs);
+    fs::create_dir_all(&target)?;
+
+    for entry in fs::read_dir(&inputs)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            fs::copy(entry.path(), target.join(entry.file_name()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_table(
+    name_a: &str,
+    times_a: &PartTimes,
+    name_b: &str,
+    times_b: &PartTimes,
+    statistic: Statistic,
+) {
+    let format = |nanos: Option<&f64>| {
+        nanos.map_or("-".into(), |nanos| {
+            format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+        })
+    };
+
+    println!(
+        "{ANSI_BOLD}{:<5}{:<6}{:>12}{:>12}{:>10}{ANSI_RESET}",
+        "Day", "Part", name_a, name_b, "Speedup"
+    );
+
+    let mut parts: Vec<&(Day, u8)> = times_a.keys().chain(times_b.keys()).collect();
+    parts.sort();
+    parts.dedup();
+
+    let (mut total_a, mut total_b) = (0.0, 0.0);
+    for key in parts {
+        let (a, b) = (times_a.get(key), times_b.get(key));
+        let speedup = match (a, b) {
+            (Some(a), Some(b)) => {
+                total_a += a;
+                total_b += b;
+                format_speedup(a / b)
+            }
+            _ => "-".into(),
+        };
+
+        println!(
+            "{:<5}{:<6}{:>12}{:>12}{:>10}",
+            key.0.to_string(),
+            part_name(key.1),
+            format(a),
+            format(b),
+            speedup
+        );
+    }
+
+    if total_b > 0.0 {
+        println!(
+            "{ANSI_BOLD}{:<11}{:>12}{:>12}{:>10}{ANSI_RESET}",
+            "Total",
+            format(Some(&total_a)),
+            format(Some(&total_b)),
+            format_speedup(total_a / total_b)
+        );
+    }
+
+    println!(
+        "\n{ANSI_ITALIC}Times are the {statistic} of all samples. A speedup above 1 means {name_b} is faster.{ANSI_RESET}"
+    );
+}
+
+fn format_speedup(ratio: f64) -> String {
+    format!("{ratio:.2}×")
+}
@@ -1,15 +1,36 @@
-use crate::template::aoc_cli;
+use crate::template::aoc_client::{self, AocClient};
+use crate::template::session;
+use crate::template::timeline::{self, Event};
 use crate::Day;
-use std::process;
+use std::{process, time::SystemTime};
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    check_session();
+
+    if let Err(e) = aoc_client::download(day) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
+    };
+
+    if let Some(year) = aoc_client::get_year() {
+        if let Err(e) = timeline::record_now(year, day, Event::Downloaded) {
+            eprintln!("failed to record download time: {e:?}");
+        }
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+/// Exits early if the session token is missing or rejected by the site.
+pub fn check_session() {
+    if let Err(e) = aoc_client::check() {
+        eprintln!("{e}");
         process::exit(1);
-    };
+    }
+
+    if let Err(e) = AocClient::from_env().and_then(|client| client.validate_session()) {
+        if session::load().is_some_and(|s| s.is_probably_expired(SystemTime::now())) {
+            eprintln!("The session token was stored more than 30 days ago.");
+        }
+        eprintln!("{e}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,139 @@
+use std::{
+    env, fs,
+    path::Path,
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    leaderboard::{get_leaderboard_path, Error, Leaderboard, Member, REFRESH_INTERVAL},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{all_days, all_days_of, Day};
+
+/// Loads the leaderboard from `file`, from the site if an id is given or `AOC_LEADERBOARD_ID` is set,
+/// and from the cache of the last fetch otherwise.
+/// A cache younger than [`REFRESH_INTERVAL`] is reused instead of fetching, unless `force` is set.
+pub fn handle(id: Option<String>, file: Option<String>, force: bool) {
+    let leaderboard = match load(id, file, force) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", render(&leaderboard));
+}
+
+fn load(id: Option<String>, file: Option<String>, force: bool) -> Result<Leaderboard, String> {
+    let id = id.or_else(|| {
+        env::var("AOC_LEADERBOARD_ID")
+            .ok()
+            .filter(|id| !id.is_empty())
+    });
+
+    let Some(year) = aoc_client::get_year() else {
+        return Err(AocClientError::YearNotSet.to_string());
+    };
+    let cached = get_leaderboard_path(year);
+
+    let json = match (file, id) {
+        (Some(file), _) => return load_file(&file),
+        (None, Some(id)) if !force => {
+            if let Some((leaderboard, age)) = load_recent(&cached, &id) {
+                println!(
+                    "{ANSI_ITALIC}Showing the leaderboard fetched {}s ago. Pass --force to fetch it again.{ANSI_RESET}",
+                    age.as_secs()
+                );
+                return Ok(leaderboard);
+            }
+            aoc_client::leaderboard(&id).map_err(|e| e.to_string())?
+        }
+        (None, Some(id)) => aoc_client::leaderboard(&id).map_err(|e| e.to_string())?,
+        (None, None) if Path::new(&cached).exists() => {
+            println!("{ANSI_ITALIC}Showing the cached leaderboard from \"{cached}\".{ANSI_RESET}");
+            return load_file(&cached);
+        }
+        (None, None) => {
+            return Err("no leaderboard id given. Pass one or set AOC_LEADERBOARD_ID.".into())
+        }
+    };
+
+    Leaderboard::parse(&json).map_err(|e| describe_error("the fetched leaderboard", e))
+}
+
+/// The cached leaderboard with `id` and its age, if it was fetched less than [`REFRESH_INTERVAL`] ago.
+fn load_recent(path: &str, id: &str) -> Option<(Leaderboard, Duration)> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age >= REFRESH_INTERVAL {
+        return None;
+    }
+
+    Leaderboard::load(path)
+        .ok()
+        .filter(|leaderboard| leaderboard.is_board(id))
+        .map(|leaderboard| (leaderboard, age))
+}
+
+pub fn load_file(path: &str) -> Result<Leaderboard, String> {
+    Leaderboard::load(path).map_err(|e| describe_error(&format!("\"{path}\""), e))
+}
+
+fn describe_error(source: &str, e: Error) -> String {
+    match e {
+        Error::Parser(e) => format!("failed to parse {source}: {e}"),
+        Error::IO(e) => format!("failed to read {source}: {e}"),
+    }
+}
+
+fn render(leaderboard: &Leaderboard) -> String {
+    let members = leaderboard.ranked();
+    let rank_width = members.len().to_string().len() + 1;
+    let score_width = members
+        .first()
+        .map_or(1, |m| m.local_score.to_string().len());
+    let indent = " ".repeat(rank_width + score_width + 2);
+
+    let mut out = format!(
+        "{ANSI_BOLD}Private leaderboard {}{ANSI_RESET}\n\n",
+        leaderboard.event
+    );
+
+    // day numbers are written vertically, tens above ones.
+    let days: Vec<Day> = match leaderboard.event.parse() {
+        Ok(year) => all_days_of(year).collect(),
+        Err(_) => all_days().collect(),
+    };
+    let tens: String = days.iter().map(|day| day.to_string().remove(0)).collect();
+    let ones: String = days.iter().map(|day| day.to_string().remove(1)).collect();
+    out.push_str(&format!("{indent}{}\n", tens.replace('0', " ")));
+    out.push_str(&format!("{indent}{ones}\n"));
+
+    for (i, member) in members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>rank_width$} {:>score_width$} {} {}\n",
+            format!("{})", i + 1),
+            member.local_score,
+            format_stars(member, &days),
+            member.display_name(),
+        ));
+    }
+
+    out
+}
+
+/// Both stars are bold, a single star is italic and a missing star is a dot.
+fn format_stars(member: &Member, days: &[Day]) -> String {
+    days.iter()
+        .map(|day| match member.stars_on(*day) {
+            0 => "·".to_string(),
+            1 => format!("{ANSI_ITALIC}*{ANSI_RESET}"),
+            _ => format!("{ANSI_BOLD}*{ANSI_RESET}"),
+        })
+        .collect()
+}
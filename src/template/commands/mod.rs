@@ -1,5 +1,15 @@
 pub mod all;
+pub mod calendar;
+pub mod compare;
 pub mod download;
+pub mod leaderboard;
+pub mod notes;
 pub mod read;
 pub mod scaffold;
+pub mod search;
+pub mod session;
 pub mod solve;
+pub mod stars;
+pub mod stats;
+pub mod status;
+pub mod wait;
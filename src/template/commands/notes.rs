@@ -0,0 +1,151 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::{self, Command},
+};
+
+use chrono::{Local, TimeZone};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    format_remaining, get_data_dir,
+    timeline::{Timeline, TIMELINE_PATH},
+    titles::Titles,
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days_of, Day};
+
+pub enum NotesCommand {
+    Open(Day),
+    Append(Day, String),
+    Report,
+}
+
+pub fn handle(command: NotesCommand) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    match command {
+        NotesCommand::Open(day) => open(year, day),
+        NotesCommand::Append(day, text) => append(year, day, &text),
+        NotesCommand::Report => report(year),
+    }
+}
+
+#[must_use]
+pub fn get_notes_path(year: u16, day: Day) -> String {
+    format!("{}/notes/{day}.md", get_data_dir(year).display())
+}
+
+/// Creates the notes file with a heading if it does not exist yet.
+fn ensure_file(path: &str, year: u16, day: Day) -> io::Result<()> {
+    if Path::new(path).exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("# {}\n", Titles::load(year).heading(day)))
+}
+
+/// Opens the notes in `$VISUAL` or `$EDITOR`, or prints them if neither is set.
+fn open(year: u16, day: Day) {
+    let path = get_notes_path(year, day);
+    if let Err(e) = ensure_file(&path, year, day) {
+        eprintln!("Failed to create notes file: {e}");
+        process::exit(1);
+    }
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty());
+
+    let Some(editor) = editor else {
+        match fs::read_to_string(&path) {
+            Ok(notes) => print!("{notes}"),
+            Err(e) => eprintln!("Failed to read notes: {e}"),
+        }
+        println!("---\nSet EDITOR to edit \"{path}\" from here.");
+        return;
+    };
+
+    // editors are often configured with arguments, e.g. `code --wait`.
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+
+    match Command::new(program).args(parts).arg(&path).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("{editor} exited with {status}."),
+        Err(e) => {
+            eprintln!("Failed to start {editor}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn append(year: u16, day: Day, text: &str) {
+    let path = get_notes_path(year, day);
+
+    let result = ensure_file(&path, year, day).and_then(|()| {
+        let mut file = OpenOptions::new().append(true).open(&path)?;
+        let time = Local::now().format("%Y-%m-%d %H:%M");
+        writeln!(file, "\n- {time}: {}", text.trim())
+    });
+
+    match result {
+        Ok(()) => println!("Added note to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write notes: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints how long every day took from its download to each correct answer.
+fn report(year: u16) {
+    let timeline = Timeline::load(TIMELINE_PATH).unwrap_or_else(|e| {
+        eprintln!("failed to load timeline: {e:?}");
+        process::exit(1);
+    });
+
+    let days: Vec<(Day, _)> = all_days_of(year)
+        .filter_map(|day| Some((day, timeline.day_times(year, day)?)))
+        .collect();
+
+    if days.is_empty() {
+        println!("No download times recorded for {year} yet.");
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}{:<5}{:<18}{:>14}{:>14}  notes{ANSI_RESET}",
+        "Day", "Downloaded", "Part 1", "Part 2"
+    );
+
+    for (day, times) in days {
+        let format = |duration: Option<_>| duration.as_ref().map_or("-".into(), format_remaining);
+        let downloaded = Local
+            .timestamp_opt(times.downloaded, 0)
+            .single()
+            .map_or_else(
+                || times.downloaded.to_string(),
+                |t| t.format("%Y-%m-%d %H:%M").to_string(),
+            );
+        let has_notes = Path::new(&get_notes_path(year, day)).exists();
+
+        println!(
+            "{:<5}{:<18}{:>14}{:>14}  {}",
+            day.to_string(),
+            downloaded,
+            format(times.part_one),
+            format(times.part_two),
+            if has_notes { "✓" } else { "" }
+        );
+    }
+}
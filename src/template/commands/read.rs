@@ -1,16 +1,39 @@
-use std::process;
+use std::{env, fs, process};
 
-use crate::template::aoc_cli;
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::markdown::{self, RenderMode};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(day: Day, plain: bool) {
+    let mode = if plain || env::var_os("NO_COLOR").is_some() {
+        RenderMode::Plain
+    } else {
+        RenderMode::Ansi
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
         process::exit(1);
     };
+
+    // prefer the description cached by `download`, only go to the network if there is none.
+    let puzzle = match fs::read_to_string(aoc_client::get_puzzle_path(year, day)) {
+        Ok(puzzle) => puzzle,
+        Err(_) => {
+            if let Err(e) = aoc_client::check() {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+
+            match aoc_client::read(day) {
+                Ok(puzzle) => puzzle,
+                Err(e) => {
+                    eprintln!("failed to read puzzle: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+    };
+
+    print!("{}", markdown::render(&puzzle, mode));
 }
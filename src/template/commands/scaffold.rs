@@ -1,12 +1,27 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    ops::Range,
+    path::Path,
     process,
 };
 
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::get_data_dir;
+use crate::template::puzzle::{Examples, Puzzle};
 use crate::Day;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+lazy_static! {
+    /// A call reading the example shared by both parts, possibly wrapped by rustfmt.
+    static ref SHARED_EXAMPLE: Regex =
+        Regex::new(r#"read_file\(\s*"examples",\s*YEAR,\s*DAY,?\s*\)"#).unwrap();
+}
+
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
 
 pub fn part_one(input: &str) -> Option<u32> {
     None
@@ -22,49 +37,235 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&EXAMPLE_PART_ONE);
+        assert_eq!(result, EXPECTED_PART_ONE);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&EXAMPLE_PART_TWO);
+        assert_eq!(result, EXPECTED_PART_TWO);
     }
 }
 "#;
 
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Reads the cached puzzle description, if there is one.
+fn read_puzzle(year: u16, day: Day) -> Option<Puzzle> {
+    fs::read_to_string(aoc_client::get_puzzle_path(year, day))
+        .ok()
+        .map(|markdown| Puzzle::parse(&markdown))
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+/// Formats the expected test result for an example answer. Only numeric answers fit the template's return type.
+fn format_expectation(answer: Option<&str>) -> String {
+    match answer.and_then(|a| a.parse::<u32>().ok()) {
+        Some(answer) => format!("Some({answer})"),
+        None => "None".into(),
+    }
+}
+
+/// The byte range of the test function `test_fn`, up to the next function.
+fn test_fn_range(module: &str, test_fn: &str) -> Option<Range<usize>> {
+    let fn_start = module.find(&format!("fn {test_fn}()"))?;
+    let fn_end = module[fn_start + 1..]
+        .find("fn ")
+        .map_or(module.len(), |i| fn_start + 1 + i);
+    Some(fn_start..fn_end)
+}
+
+/// Replaces the `None` assertion in the test function `test_fn` with `expected`.
+/// Returns [`None`] if the test has no `None` assertion left to fill.
+fn fill_expectation(module: &str, test_fn: &str, expected: &str) -> Option<String> {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let range = test_fn_range(module, test_fn)?;
+    let assert_pos = range.start + module[range].find(PLACEHOLDER)?;
+
+    let mut filled = module.to_string();
+    filled.replace_range(
+        assert_pos..assert_pos + PLACEHOLDER.len(),
+        &format!("assert_eq!(result, {expected});"),
+    );
+    Some(filled)
+}
+
+/// Makes the test function `test_fn` read the example of `part` instead of the shared example.
+/// Returns [`None`] if the test does not read the shared example.
+fn use_example_part(module: &str, test_fn: &str, part: u8) -> Option<String> {
+    let range = test_fn_range(module, test_fn)?;
+    let call = SHARED_EXAMPLE.find(&module[range.clone()])?;
+
+    let mut updated = module.to_string();
+    updated.replace_range(
+        range.start + call.start()..range.start + call.end(),
+        &format!(r#"read_file_part("examples", YEAR, DAY, {part})"#),
+    );
+    Some(updated)
+}
+
+/// Fills expectations of an existing module that are still `None`, e.g. after part two was downloaded.
+/// With `split_examples`, tests that read the shared example are switched to the example of their part.
+fn update_module(module_path: &str, expectations: &[(&str, String)], split_examples: bool) {
+    let Ok(mut module) = fs::read_to_string(module_path) else {
+        eprintln!("Failed to read module file \"{module_path}\"");
+        process::exit(1);
+    };
+
+    let mut updated = vec![];
+    let mut split = vec![];
+    for (part, (test_fn, expected)) in (1..).zip(expectations) {
+        if split_examples {
+            if let Some(updated) = use_example_part(&module, test_fn, part) {
+                module = updated;
+                split.push(*test_fn);
+            }
+        }
+        if expected == "None" {
+            continue;
+        }
+        if let Some(filled) = fill_expectation(&module, test_fn, expected) {
+            module = filled;
+            updated.push(*test_fn);
+        }
+    }
+
+    if updated.is_empty() && split.is_empty() {
+        println!("Module file \"{module_path}\" already exists, no expectations to fill in.");
+        return;
+    }
+
+    match fs::write(module_path, module) {
+        Ok(()) => {
+            if !split.is_empty() {
+                println!(
+                    "Switched {} to the example of its part in \"{module_path}\"",
+                    split.join(", ")
+                );
+            }
+            if !updated.is_empty() {
+                println!(
+                    "Filled in expected example results of {} in \"{module_path}\"",
+                    updated.join(", ")
+                );
+            }
+        }
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to update module file: {e}");
             process::exit(1);
         }
+    }
+}
+
+fn write_example(path: &str, example: Option<&str>) {
+    let Some(example) = example else {
+        match create_file(path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+        return;
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    if fs::read_to_string(path).is_ok_and(|existing| !existing.trim().is_empty()) {
+        println!("Kept existing example file \"{}\"", &path);
+        return;
+    }
+
+    match fs::write(path, example) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Wrote example from puzzle description to \"{}\"", &path);
         }
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(day: Day) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let input_path = aoc_client::get_input_path(year, day);
+    let module_path = get_path_for_bin(year, day);
+    let examples_dir = get_data_dir(year).join("examples");
+
+    let puzzle = read_puzzle(year, day);
+    let examples = puzzle.as_ref().map(Puzzle::examples).unwrap_or_default();
+    let expected_part_one =
+        format_expectation(puzzle.as_ref().and_then(|p| p.answer(1)).as_deref());
+    let expected_part_two =
+        format_expectation(puzzle.as_ref().and_then(|p| p.answer(2)).as_deref());
+    let (example_part_one, example_part_two) = match &examples {
+        Examples {
+            part_one: Some(_),
+            part_two: Some(_),
+        } => (
+            r#"advent_of_code::template::read_file_part("examples", YEAR, DAY, 1)"#,
+            r#"advent_of_code::template::read_file_part("examples", YEAR, DAY, 2)"#,
+        ),
+        _ => (
+            r#"advent_of_code::template::read_file("examples", YEAR, DAY)"#,
+            r#"advent_of_code::template::read_file("examples", YEAR, DAY)"#,
+        ),
+    };
+
+    match safe_create_file(&module_path) {
+        Ok(mut file) => match file.write_all(
+            MODULE_TEMPLATE
+                .replace("YEAR_NUMBER", &year.to_string())
+                .replace("DAY_NUMBER", &day.into_inner().to_string())
+                .replace("EXAMPLE_PART_ONE", example_part_one)
+                .replace("EXAMPLE_PART_TWO", example_part_two)
+                .replace("EXPECTED_PART_ONE", &expected_part_one)
+                .replace("EXPECTED_PART_TWO", &expected_part_two)
+                .as_bytes(),
+        ) {
+            Ok(()) => {
+                println!("Created module file \"{}\"", &module_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write module contents: {e}");
+                process::exit(1);
+            }
+        },
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => update_module(
+            &module_path,
+            &[
+                ("test_part_one", expected_part_one),
+                ("test_part_two", expected_part_two),
+            ],
+            examples.part_two.is_some(),
+        ),
+        Err(e) => {
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
     }
@@ -79,16 +280,77 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match examples {
+        Examples {
+            part_one: Some(part_one),
+            part_two: Some(part_two),
+        } => {
+            let path = |part| format!("{}/{day}-{part}.txt", examples_dir.display());
+            write_example(&path(1), Some(&part_one));
+            write_example(&path(2), Some(&part_two));
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        Examples { part_one, .. } => {
+            let path = format!("{}/{day}.txt", examples_dir.display());
+            write_example(&path, part_one.as_deref());
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_expectation, format_expectation, use_example_part, MODULE_TEMPLATE};
+
+    fn get_mock_module() -> String {
+        MODULE_TEMPLATE
+            .replace(
+                "EXAMPLE_PART_ONE",
+                r#"advent_of_code::template::read_file("examples", YEAR, DAY)"#,
+            )
+            .replace(
+                "EXAMPLE_PART_TWO",
+                "advent_of_code::template::read_file(\n            \"examples\", YEAR, DAY,\n        )",
+            )
+            .replace("EXPECTED_PART_ONE", "Some(142)")
+            .replace("EXPECTED_PART_TWO", "None")
+    }
+
+    #[test]
+    fn formats_expectations() {
+        assert_eq!(format_expectation(Some("4361")), "Some(4361)");
+        assert_eq!(format_expectation(Some("CMZ")), "None");
+        assert_eq!(format_expectation(None), "None");
+    }
+
+    #[test]
+    fn fills_missing_expectations() {
+        let module = get_mock_module();
+
+        let filled = fill_expectation(&module, "test_part_two", "Some(281)").unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(142));"));
+        assert!(filled.contains("assert_eq!(result, Some(281));"));
+        assert!(!filled.contains("assert_eq!(result, None);"));
+
+        assert!(fill_expectation(&module, "test_part_one", "Some(1)").is_none());
+        assert!(fill_expectation(&filled, "test_part_two", "Some(1)").is_none());
+    }
+
+    #[test]
+    fn splits_shared_examples() {
+        let module = get_mock_module();
+
+        let split = use_example_part(&module, "test_part_one", 1).unwrap();
+        let split = use_example_part(&split, "test_part_two", 2).unwrap();
+        assert!(split.contains(
+            r#"part_one(&advent_of_code::template::read_file_part("examples", YEAR, DAY, 1));"#
+        ));
+        assert!(split.contains(
+            r#"part_two(&advent_of_code::template::read_file_part("examples", YEAR, DAY, 2));"#
+        ));
+
+        assert!(use_example_part(&split, "test_part_one", 1).is_none());
+        assert!(use_example_part(&split, "test_part_two", 2).is_none());
+    }
 }
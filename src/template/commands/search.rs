@@ -0,0 +1,68 @@
+use std::{env, process};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    markdown::RenderMode,
+    search::Index,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Maximum number of days to list.
+const MAX_RESULTS: usize = 5;
+
+pub fn handle(query: &str, plain: bool) {
+    let mode = if plain || env::var_os("NO_COLOR").is_some() {
+        RenderMode::Plain
+    } else {
+        RenderMode::Ansi
+    };
+
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    if query.trim().is_empty() {
+        eprintln!("No search terms given.");
+        process::exit(1);
+    }
+
+    let index = Index::load(year);
+    if index.is_empty() {
+        eprintln!("No cached puzzle descriptions found. Run `cargo download <day>` or `cargo read <day>` first.");
+        process::exit(1);
+    }
+
+    let matches = index.search(query);
+    if matches.is_empty() {
+        println!("No puzzle mentions \"{query}\".");
+        return;
+    }
+
+    for (i, found) in matches.iter().take(MAX_RESULTS).enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let heading = if found.title.is_empty() {
+            format!("Day {}", found.day)
+        } else {
+            format!("Day {}: {}", found.day, found.title)
+        };
+        match mode {
+            RenderMode::Ansi => println!(
+                "{ANSI_BOLD}{heading}{ANSI_RESET} {ANSI_ITALIC}(score {:.2}){ANSI_RESET}",
+                found.score
+            ),
+            RenderMode::Plain => println!("{heading} (score {:.2})", found.score),
+        }
+
+        for snippet in &found.snippets {
+            println!("    {}", snippet.highlight(mode));
+        }
+    }
+
+    if matches.len() > MAX_RESULTS {
+        println!("\n{} more days match.", matches.len() - MAX_RESULTS);
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs, process, time::SystemTime};
+
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    session::{self, SessionSource},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+pub enum SessionCommand {
+    Set(String),
+    Show,
+    Validate,
+    Clear,
+}
+
+pub fn handle(command: SessionCommand) {
+    match command {
+        SessionCommand::Set(token) => set(&token),
+        SessionCommand::Show => show(),
+        SessionCommand::Validate => validate(),
+        SessionCommand::Clear => clear(),
+    }
+}
+
+fn set(token: &str) {
+    if token.trim().is_empty() {
+        eprintln!("Refusing to store an empty session token.");
+        process::exit(1);
+    }
+
+    match session::store(token) {
+        Ok(path) => println!("Stored session token in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to store session token: {e}");
+            process::exit(1);
+        }
+    }
+
+    if std::env::var("AOC_SESSION").is_ok_and(|t| !t.trim().is_empty()) {
+        println!("Note: AOC_SESSION is set and takes precedence over the stored token.");
+    }
+}
+
+fn show() {
+    let Some(session) = session::load() else {
+        eprintln!("{}", AocClientError::SessionNotFound);
+        process::exit(1);
+    };
+
+    println!("{ANSI_BOLD}Token:{ANSI_RESET}  {}", session.masked());
+    println!("{ANSI_BOLD}Source:{ANSI_RESET} {}", session.source);
+
+    if session.is_probably_expired(SystemTime::now()) {
+        println!(
+            "{ANSI_ITALIC}The token was stored more than 30 days ago and has probably expired.{ANSI_RESET}"
+        );
+    }
+}
+
+fn validate() {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match client.validate_session() {
+        Ok(user) => println!("Session is valid. Logged in as {ANSI_BOLD}{user}{ANSI_RESET}."),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn clear() {
+    let Some(session) = session::load() else {
+        println!("No session token stored.");
+        return;
+    };
+
+    match &session.source {
+        SessionSource::Env => {
+            eprintln!("The active token comes from AOC_SESSION. Unset the variable to clear it.");
+            process::exit(1);
+        }
+        SessionSource::ConfigFile(path) | SessionSource::HomeFile(path) => {
+            if let Err(e) = fs::remove_file(path) {
+                eprintln!("Failed to remove \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+            println!("Removed session token from \"{}\".", path.display());
+        }
+    }
+
+    if let Some(remaining) = session::load() {
+        println!("Another token is still configured in {}.", remaining.source);
+    }
+}
@@ -1,9 +1,31 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    benchmark::BenchOverrides,
+    commands::all::get_bin_name,
+};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// `bench` is forwarded to the solution if `time` is set.
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    bench: &BenchOverrides,
+    submit_part: Option<u8>,
+    wait: bool,
+) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        get_bin_name(year, day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -14,10 +36,15 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if wait {
+            cmd_args.push("--wait".to_string());
+        }
     }
 
     if time {
         cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
     }
 
     let mut cmd = Command::new("cargo")
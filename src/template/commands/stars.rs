@@ -0,0 +1,66 @@
+use std::{fs, process};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    commands::{leaderboard::load_file, stats::find_member},
+    leaderboard::get_leaderboard_path,
+    readme_stars,
+    submissions::{Ledger, LEDGER_PATH},
+};
+
+/// Updates the README stars table from the submission ledger,
+/// or from a leaderboard file if `leaderboard` is set or a `file` is given.
+/// Stars already listed in the table are kept.
+pub fn handle(leaderboard: bool, file: Option<String>, member_id: Option<u64>) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let stars = if leaderboard || file.is_some() {
+        let path = file.unwrap_or_else(|| get_leaderboard_path(year));
+        let leaderboard = load_file(&path).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+        if leaderboard.event != year.to_string() {
+            eprintln!(
+                "The leaderboard is for {}, but AOC_YEAR is {year}.",
+                leaderboard.event
+            );
+            process::exit(1);
+        }
+
+        let Some(member) = find_member(&leaderboard, member_id) else {
+            eprintln!(
+                "Could not determine whose stars to show. Pass --member <id> or set AOC_USER_ID."
+            );
+            process::exit(1);
+        };
+        readme_stars::from_member(member, year)
+    } else {
+        match Ledger::load(LEDGER_PATH) {
+            Ok(ledger) => readme_stars::from_ledger(&ledger, year),
+            Err(e) => {
+                eprintln!("failed to load submission ledger: {e:?}");
+                process::exit(1);
+            }
+        }
+    };
+
+    let listed = fs::read_to_string("README.md")
+        .ok()
+        .and_then(|readme| readme_stars::read(&readme, year).ok())
+        .unwrap_or_default();
+    let stars = readme_stars::merge(&listed, &stars);
+
+    let count: usize = stars.iter().map(|(_, count)| count).sum();
+    match readme_stars::update(year, &stars) {
+        Ok(()) => println!("Successfully updated README with {count} stars."),
+        Err(_) => {
+            eprintln!("Failed to update readme with stars.");
+            process::exit(1);
+        }
+    }
+}
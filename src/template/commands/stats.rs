@@ -0,0 +1,100 @@
+use std::{env, path::Path, process};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    commands::leaderboard::load_file,
+    format_remaining,
+    leaderboard::{get_leaderboard_path, Leaderboard, Member},
+    stats::{self, DayStats},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Reports completion times from a cached leaderboard, by default the one stored by `leaderboard`.
+pub fn handle(member_id: Option<u64>, file: Option<String>) {
+    let path = file.unwrap_or_else(|| {
+        let Some(year) = aoc_client::get_year() else {
+            eprintln!("{}", AocClientError::YearNotSet);
+            process::exit(1);
+        };
+        get_leaderboard_path(year)
+    });
+    if !Path::new(&path).exists() {
+        eprintln!(
+            "No leaderboard found at \"{path}\". Fetch one with `cargo leaderboard <id>` first."
+        );
+        process::exit(1);
+    }
+
+    let leaderboard = load_file(&path).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let Some(member) = find_member(&leaderboard, member_id) else {
+        eprintln!(
+            "Could not determine whose stats to show. Pass --member <id> or set AOC_USER_ID."
+        );
+        process::exit(1);
+    };
+
+    let Ok(year) = leaderboard.event.parse::<u16>() else {
+        eprintln!(
+            "The leaderboard has an invalid event: {}",
+            leaderboard.event
+        );
+        process::exit(1);
+    };
+    if aoc_client::get_year().is_some_and(|configured| configured != year) {
+        println!("{ANSI_ITALIC}Note: the leaderboard is for {year}, not AOC_YEAR.{ANSI_RESET}");
+    }
+
+    let days = stats::day_stats(member, year);
+    let streaks = stats::streaks(&days);
+
+    println!(
+        "{ANSI_BOLD}{} in {year}{ANSI_RESET} ({} stars)\n",
+        member.display_name(),
+        member.stars
+    );
+    println!(
+        "{ANSI_BOLD}{:<5}{:>14}{:>14}{:>14}{ANSI_RESET}",
+        "Day", "Part 1", "Part 2", "Gap"
+    );
+    for day in &days {
+        print_day(day);
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Longest streak:{ANSI_RESET} {} days",
+        streaks.longest
+    );
+    println!(
+        "{ANSI_BOLD}Longest same-day streak:{ANSI_RESET} {} days",
+        streaks.same_day
+    );
+}
+
+/// The requested member, the one set in `AOC_USER_ID`, or the only member of the leaderboard.
+pub fn find_member(leaderboard: &Leaderboard, member_id: Option<u64>) -> Option<&Member> {
+    let member_id =
+        member_id.or_else(|| env::var("AOC_USER_ID").ok().and_then(|id| id.parse().ok()));
+
+    match member_id {
+        Some(id) => leaderboard.member(id),
+        None if leaderboard.members.len() == 1 => leaderboard.members.values().next(),
+        None => None,
+    }
+}
+
+fn print_day(day: &DayStats) {
+    let format = |duration: Option<_>| duration.as_ref().map_or("-".into(), format_remaining);
+
+    println!(
+        "{:<5}{:>14}{:>14}{:>14}",
+        day.day.to_string(),
+        format(day.part_one),
+        format(day.part_two),
+        format(day.gap()),
+    );
+}
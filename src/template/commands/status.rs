@@ -0,0 +1,146 @@
+use std::{
+    fs,
+    path::Path,
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    commands::all::{get_bin_name, get_path_for_bin},
+    get_data_dir, readme_stars,
+    status::DayStatus,
+    submissions::{Ledger, LEDGER_PATH},
+    titles::Titles,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{all_days_of, Day};
+
+/// Prints a matrix of what exists for every day. Unless `skip_run` is set,
+/// the tests of every solution are run, and solutions of days with stars are run against their input.
+pub fn handle(skip_run: bool) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let readme_stars = fs::read_to_string("README.md")
+        .ok()
+        .and_then(|readme| readme_stars::read(&readme, year).ok())
+        .unwrap_or_default();
+    let ledger = Ledger::load(LEDGER_PATH).unwrap_or_else(|e| {
+        eprintln!("failed to load submission ledger: {e:?}");
+        process::exit(1);
+    });
+
+    println!(
+        "{ANSI_BOLD}{:<5}{:^7}{:^9}{:^7}{:^8}{:^7}{:^7} title{ANSI_RESET}",
+        "Day", "bin", "example", "input", "puzzle", "tests", "stars"
+    );
+
+    let titles = Titles::load(year);
+    let examples = get_data_dir(year).join("examples");
+    let mut issues = vec![];
+
+    for day in all_days_of(year) {
+        let has_bin = fs::metadata(get_path_for_bin(year, day)).is_ok();
+        let run = has_bin && !skip_run;
+
+        let mut status = DayStatus {
+            has_bin,
+            has_example: has_content(examples.join(format!("{day}.txt")))
+                || has_content(examples.join(format!("{day}-1.txt"))),
+            has_input: has_content(aoc_client::get_input_path(year, day)),
+            has_puzzle: has_content(aoc_client::get_puzzle_path(year, day)),
+            tests_pass: run.then(|| run_tests(year, day)),
+            stars: readme_stars
+                .iter()
+                .find(|(d, _)| *d == day)
+                .map_or(0, |(_, stars)| *stars),
+            solved_parts: (1..=2)
+                .filter(|part| ledger.is_solved(year, day, *part))
+                .count(),
+            returns: None,
+        };
+
+        if run && status.has_input && status.stars > 0 {
+            status.returns = run_solution(year, day);
+        }
+
+        print_row(day, &status, titles.get(day));
+        issues.extend(status.inconsistencies(year, day));
+    }
+
+    if !issues.is_empty() {
+        println!("\n{ANSI_BOLD}Inconsistencies:{ANSI_RESET}");
+        for issue in issues {
+            println!("  {issue}");
+        }
+    }
+
+    if skip_run {
+        println!("\n{ANSI_ITALIC}Tests and solutions were not run.{ANSI_RESET}");
+    }
+}
+
+fn has_content(path: impl AsRef<Path>) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn run_tests(year: u16, day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &get_bin_name(year, day)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Runs the solution against the real input and checks which parts returned a result.
+fn run_solution(year: u16, day: Day) -> Option<[bool; 2]> {
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &get_bin_name(year, day),
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // intermediate results are overwritten with a carriage return.
+    let lines: Vec<&str> = stdout.split(['\r', '\n']).collect();
+    let returns = |part: u8| {
+        let prefix = format!("Part {part}: ");
+        lines
+            .iter()
+            .rev()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .is_some_and(|result| !result.starts_with('✖'))
+    };
+
+    Some([returns(1), returns(2)])
+}
+
+fn print_row(day: Day, status: &DayStatus, title: Option<&str>) {
+    let flag = |value: bool| if value { "✓" } else { "·" };
+    let tests = match status.tests_pass {
+        Some(true) => "✓",
+        Some(false) => "✖",
+        None => "-",
+    };
+
+    println!(
+        "{:<5}{:^7}{:^9}{:^7}{:^8}{:^7}{:^7} {}",
+        day.to_string(),
+        flag(status.has_bin),
+        flag(status.has_example),
+        flag(status.has_input),
+        flag(status.has_puzzle),
+        tests,
+        "*".repeat(status.stars),
+        title.unwrap_or_default(),
+    );
+}
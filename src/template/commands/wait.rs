@@ -0,0 +1,32 @@
+use std::{process, time::Duration};
+
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    commands::{download, scaffold},
+    countdown,
+    unlock::{self, Clock},
+};
+
+/// Extra wait after the unlock so the puzzle is reliably available.
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+pub fn handle(clock: &impl Clock) {
+    let Some(year) = aoc_client::get_year() else {
+        eprintln!("{}", AocClientError::YearNotSet);
+        process::exit(1);
+    };
+
+    let Some((day, remaining)) = unlock::next_unlock(year, clock) else {
+        println!("Every puzzle of {year} is already unlocked.");
+        return;
+    };
+
+    // fail before the countdown rather than at midnight.
+    download::check_session();
+
+    let remaining = remaining.to_std().unwrap_or_default() + UNLOCK_GRACE;
+    countdown(&format!("Day {day} of {year} unlocks in"), remaining);
+
+    download::handle(day);
+    scaffold::handle(day);
+}
@@ -0,0 +1,119 @@
+/// Persisted submission cooldown.
+/// When the site answers "You gave an answer too recently", the deadline is stored so later submissions can wait for it.
+/// The site limits answers per account, so the cooldown applies to every puzzle.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const COOLDOWN_PATH: &str = "data/cooldowns.tsv";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+pub struct Cooldowns {
+    path: PathBuf,
+    /// Unix timestamp (seconds) until which submissions are blocked.
+    deadline: Option<i64>,
+}
+
+impl Cooldowns {
+    /// Loads the cooldown at `path`. A missing file is treated as no active cooldown.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+
+        let deadline = match fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, deadline })
+    }
+
+    /// Time left until submissions are accepted again, if a cooldown is active at `now`.
+    pub fn remaining(&self, now: i64) -> Option<Duration> {
+        let remaining = u64::try_from(self.deadline? - now).ok()?;
+        (remaining > 0).then(|| Duration::from_secs(remaining))
+    }
+
+    /// Stores the deadline of a cooldown.
+    pub fn set(&mut self, deadline: i64) -> Result<(), Error> {
+        self.deadline = Some(deadline);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, format!("{deadline}\n"))?;
+        Ok(())
+    }
+}
+
+/// The deadline is the last field of a line. Earlier versions stored one line per puzzle, the latest of them applies.
+fn parse(contents: &str) -> Result<Option<i64>, Error> {
+    let mut latest = None;
+
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let deadline = line
+            .split('\t')
+            .next_back()
+            .and_then(|deadline| deadline.trim().parse::<i64>().ok())
+            .ok_or_else(|| Error::Parser(format!("malformed cooldown line: {line}")))?;
+        latest = latest.max(Some(deadline));
+    }
+
+    Ok(latest)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Cooldowns};
+    use std::time::Duration;
+
+    #[test]
+    fn computes_remaining_time() {
+        let cooldowns = Cooldowns {
+            path: "unused.tsv".into(),
+            deadline: parse("1000\n").unwrap(),
+        };
+
+        assert_eq!(cooldowns.remaining(700), Some(Duration::from_secs(300)));
+        assert_eq!(cooldowns.remaining(1000), None);
+        assert_eq!(cooldowns.remaining(1200), None);
+    }
+
+    #[test]
+    fn parses_deadlines() {
+        assert_eq!(parse("").unwrap(), None);
+        assert_eq!(parse("1000\n").unwrap(), Some(1000));
+        assert_eq!(
+            parse("2023\t07\t1000\n2023\t08\t900\n").unwrap(),
+            Some(1000)
+        );
+        assert!(parse("soon\n").is_err());
+    }
+
+    #[test]
+    fn stores_deadline() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-cooldown-test-{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        Cooldowns::load(&path).unwrap().set(1000).unwrap();
+        assert_eq!(
+            Cooldowns::load(&path).unwrap().remaining(400),
+            Some(Duration::from_secs(600))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,184 @@
+/// Private leaderboards as returned by `/{year}/leaderboard/private/view/{id}.json`.
+use std::{collections::BTreeMap, fs, io, path::Path, time::Duration};
+
+use serde::Deserialize;
+
+use crate::template::get_data_dir;
+use crate::Day;
+
+/// The most recently fetched leaderboard of a year.
+#[must_use]
+pub fn get_leaderboard_path(year: u16) -> String {
+    format!("{}/leaderboard.json", get_data_dir(year).display())
+}
+
+/// The site asks to fetch a private leaderboard at most once per 15 minutes.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    #[serde(default)]
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// Stars keyed by day and part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of the moment the star was earned.
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Whether this is the leaderboard with `id`. Leaderboards without an owner are assumed to match.
+    pub fn is_board(&self, id: &str) -> bool {
+        self.owner_id == 0 || id.trim().parse() == Ok(self.owner_id)
+    }
+
+    pub fn member(&self, id: u64) -> Option<&Member> {
+        self.members.values().find(|member| member.id == id)
+    }
+
+    /// Members ordered like on the site: by local score, then stars, then who got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+}
+
+impl Member {
+    /// The name shown on the site, which falls back to the id for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// The number of stars earned on a day, from 0 to 2.
+    pub fn stars_on(&self, day: Day) -> usize {
+        self.completion_day_level
+            .get(&day.into_inner())
+            .map_or(0, BTreeMap::len)
+    }
+
+    /// When the star of a part was earned, if it was.
+    pub fn star_ts(&self, day: Day, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.into_inner())?
+            .get(&part)
+            .map(|star| star.get_star_ts)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Leaderboard;
+    use crate::day;
+
+    fn get_mock_leaderboard() -> String {
+        r#"{
+            "event": "2023",
+            "owner_id": 1,
+            "members": {
+                "1": {
+                    "id": 1, "name": "Jane Doe", "stars": 3, "local_score": 10, "global_score": 0,
+                    "last_star_ts": 1701500000,
+                    "completion_day_level": {
+                        "1": {
+                            "1": {"get_star_ts": 1701407000, "star_index": 10},
+                            "2": {"get_star_ts": 1701408000, "star_index": 20}
+                        },
+                        "2": {"1": {"get_star_ts": 1701500000, "star_index": 30}}
+                    }
+                },
+                "2": {
+                    "id": 2, "name": null, "stars": 4, "local_score": 10, "global_score": 0,
+                    "last_star_ts": 1701600000, "completion_day_level": {}
+                },
+                "3": {
+                    "id": 3, "name": "Late", "stars": 0, "local_score": 0, "global_score": 0,
+                    "last_star_ts": 0, "completion_day_level": {}
+                }
+            }
+        }"#
+        .into()
+    }
+
+    #[test]
+    fn ranks_members() {
+        let leaderboard = Leaderboard::parse(&get_mock_leaderboard()).unwrap();
+        let ranked: Vec<u64> = leaderboard.ranked().iter().map(|m| m.id).collect();
+
+        assert_eq!(leaderboard.event, "2023");
+        assert_eq!(ranked, vec![2, 1, 3]);
+        assert_eq!(
+            leaderboard.members["2"].display_name(),
+            "(anonymous user #2)"
+        );
+    }
+
+    #[test]
+    fn reads_stars() {
+        let leaderboard = Leaderboard::parse(&get_mock_leaderboard()).unwrap();
+        let member = &leaderboard.members["1"];
+
+        assert_eq!(member.stars_on(day!(1)), 2);
+        assert_eq!(member.stars_on(day!(2)), 1);
+        assert_eq!(member.stars_on(day!(3)), 0);
+        assert_eq!(member.star_ts(day!(1), 2), Some(1701408000));
+        assert_eq!(member.star_ts(day!(2), 2), None);
+        assert!(Leaderboard::parse("{\"members\": []}").is_err());
+    }
+
+    #[test]
+    fn matches_board_id() {
+        let leaderboard = Leaderboard::parse(&get_mock_leaderboard()).unwrap();
+
+        assert!(leaderboard.is_board("1"));
+        assert!(!leaderboard.is_board("123456"));
+        assert!(Leaderboard::parse("{\"event\": \"2023\", \"members\": {}}")
+            .unwrap()
+            .is_board("123456"));
+    }
+}
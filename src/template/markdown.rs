@@ -0,0 +1,252 @@
+/// Terminal renderer for cached puzzle descriptions.
+/// Supports the markdown subset written by `download`: headings, emphasis, code spans and blocks, links and lists.
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Styled output using ANSI escape codes.
+    Ansi,
+    /// Unstyled output that is safe to pipe into a pager or file.
+    Plain,
+}
+
+lazy_static! {
+    static ref LINK: Regex = Regex::new(r"\[((?:\\.|[^\]\\])*)\]\(([^)]*)\)").unwrap();
+    static ref HEADING: Regex = Regex::new(r"^#{1,6}\s+(.*)$").unwrap();
+    static ref SETEXT_UNDERLINE: Regex = Regex::new(r"^(-{3,}|={3,})\s*$").unwrap();
+    static ref LIST_ITEM: Regex = Regex::new(r"^\s*[-*]\s+(.*)$").unwrap();
+}
+
+/// Renders markdown for display in a terminal.
+pub fn render(markdown: &str, mode: RenderMode) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out: Vec<String> = vec![];
+    let mut in_code_block = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if let Some(code) = code_block_toggle(line, &mut in_code_block) {
+            if !code.is_empty() {
+                out.push(format!("    {code}"));
+            }
+            continue;
+        }
+
+        if in_code_block {
+            out.push(format!("    {}", decode_entities(line)));
+            continue;
+        }
+
+        // setext headings, e.g. a line followed by `-----`.
+        if !line.trim().is_empty() && lines.get(i).is_some_and(|l| SETEXT_UNDERLINE.is_match(l)) {
+            i += 1;
+            out.push(style_heading(&render_inline(line, mode), mode));
+            continue;
+        }
+
+        if let Some(c) = HEADING.captures(line) {
+            out.push(style_heading(&render_inline(&c[1], mode), mode));
+        } else if let Some(c) = LIST_ITEM.captures(line) {
+            let bullet = match mode {
+                RenderMode::Ansi => "•",
+                RenderMode::Plain => "-",
+            };
+            out.push(format!("  {bullet} {}", render_inline(&c[1], mode)));
+        } else {
+            out.push(render_inline(line, mode));
+        }
+    }
+
+    let mut rendered = out.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+/// Handles code fences and raw `<pre><code>` tags.
+/// Returns the text on the line outside of the tag if the line opened or closed a code block.
+fn code_block_toggle(line: &str, in_code_block: &mut bool) -> Option<String> {
+    let trimmed = line.trim();
+
+    if trimmed.starts_with("```") {
+        *in_code_block = !*in_code_block;
+        return Some(String::new());
+    }
+
+    if !*in_code_block {
+        let rest = trimmed.strip_prefix("<pre><code>")?;
+        *in_code_block = true;
+        if let Some(code) = rest.strip_suffix("</code></pre>") {
+            *in_code_block = false;
+            return Some(decode_entities(code));
+        }
+        return Some(decode_entities(rest));
+    }
+
+    let code = trimmed.strip_suffix("</code></pre>")?;
+    *in_code_block = false;
+    Some(decode_entities(code))
+}
+
+fn style_heading(text: &str, mode: RenderMode) -> String {
+    match mode {
+        RenderMode::Ansi => format!("{ANSI_BOLD}{text}{ANSI_RESET}"),
+        RenderMode::Plain => text.to_string(),
+    }
+}
+
+/// Renders emphasis, code spans, links and escapes within a single line.
+fn render_inline(text: &str, mode: RenderMode) -> String {
+    let text = LINK.replace_all(text, |c: &regex::Captures| {
+        let url = &c[2];
+        match mode {
+            RenderMode::Ansi => format!("{} {ANSI_ITALIC}<{url}>{ANSI_RESET}", &c[1]),
+            RenderMode::Plain => format!("{} <{url}>", &c[1]),
+        }
+    });
+
+    let mut out = String::new();
+    let (mut in_code, mut in_emphasis) = (false, false);
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '`' => {
+                in_code = !in_code;
+                apply_style(&mut out, in_code, in_emphasis, mode);
+            }
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                }
+                in_emphasis = !in_emphasis;
+                apply_style(&mut out, in_code, in_emphasis, mode);
+            }
+            '\x1b' => {
+                // keep escape sequences produced by link rendering intact.
+                out.push(c);
+                for next in chars.by_ref() {
+                    out.push(next);
+                    if next == 'm' {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    if mode == RenderMode::Ansi && (in_code || in_emphasis) {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+fn apply_style(out: &mut String, in_code: bool, in_emphasis: bool, mode: RenderMode) {
+    if mode == RenderMode::Plain {
+        return;
+    }
+
+    out.push_str(ANSI_RESET);
+    if in_code {
+        out.push_str(ANSI_ITALIC);
+    }
+    if in_emphasis {
+        out.push_str(ANSI_BOLD);
+    }
+}
+
+/// Strips markdown syntax from a single line, e.g. for titles or search snippets.
+pub fn to_plain_text(line: &str) -> String {
+    render_inline(line, RenderMode::Plain)
+}
+
+/// Decodes the HTML entities that can appear in raw `<pre><code>` blocks.
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, to_plain_text, RenderMode};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    fn get_mock_puzzle() -> String {
+        [
+            "## --- Day 3: Gear Ratios ---",
+            "",
+            "A *gear* is any `\\*` symbol. See [this](https://example.com).",
+            "",
+            "```",
+            "467..114..",
+            "...*......",
+            "```",
+            "",
+            "- Sum is `*4361*`.",
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn renders_plain() {
+        let expected = [
+            "--- Day 3: Gear Ratios ---",
+            "",
+            "A gear is any * symbol. See this <https://example.com>.",
+            "",
+            "    467..114..",
+            "    ...*......",
+            "",
+            "  - Sum is 4361.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&get_mock_puzzle(), RenderMode::Plain), expected);
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let rendered = render(&get_mock_puzzle(), RenderMode::Ansi);
+
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 3: Gear Ratios ---{ANSI_RESET}\n"
+        )));
+        assert!(rendered.contains(&format!("A {ANSI_RESET}{ANSI_BOLD}gear{ANSI_RESET} is")));
+        assert!(rendered.contains(&format!(
+            "{ANSI_RESET}{ANSI_ITALIC}{ANSI_BOLD}4361{ANSI_RESET}{ANSI_ITALIC}{ANSI_RESET}"
+        )));
+        assert!(rendered.contains("  • Sum is"));
+    }
+
+    #[test]
+    fn renders_legacy_formats() {
+        let legacy = "\\--- Day 1: Trebuchet?! ---\n----------\n\n<pre><code>1abc2\na1b2c3&lt;\n</code></pre>\n";
+        let expected = "--- Day 1: Trebuchet?! ---\n\n    1abc2\n    a1b2c3<\n";
+
+        assert_eq!(render(legacy, RenderMode::Plain), expected);
+    }
+
+    #[test]
+    fn strips_inline_syntax() {
+        assert_eq!(
+            to_plain_text("Play *Camel Cards* with `\\*`"),
+            "Play Camel Cards with *"
+        );
+    }
+}
@@ -1,48 +1,135 @@
 use crate::Day;
-use std::{env, fs};
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod benchmark;
+pub mod calendar;
+pub mod child_commands;
 pub mod commands;
+pub mod cooldown;
+pub mod leaderboard;
+pub mod markdown;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod search;
+pub mod session;
+pub mod stats;
+pub mod status;
+pub mod submissions;
+pub mod timeline;
+pub mod titles;
+pub mod unlock;
+pub mod verdict;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Directory that holds the inputs, examples and puzzle descriptions of a year, e.g. `data/2023`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn get_data_dir(year: u16) -> PathBuf {
+    PathBuf::from("data").join(year.to_string())
+}
+
+/// Helper function that reads a text file of a year to a string.
+#[must_use]
+pub fn read_file(folder: &str, year: u16, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(get_data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: u16, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(get_data_dir(year))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Blocks for `duration` while showing a countdown on a single terminal line.
+pub fn countdown(label: &str, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    let mut stdout = stdout();
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        print!(
+            "\r{label} {ANSI_ITALIC}{}{ANSI_RESET}   ",
+            format_remaining(&remaining)
+        );
+        let _ = stdout.flush();
+
+        if remaining.is_zero() {
+            break;
+        }
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!();
+}
+
+/// Formats a remaining duration like `1h 02m 03s`, `5m 02s` or `42s`.
+#[must_use]
+pub fn format_remaining(duration: &Duration) -> String {
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+///
+/// With a third argument, the input is first passed to that parse function, which is timed on its own.
+/// Both parts then receive a reference to its output instead of the input.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($year:expr, $day:expr, $parse:ident) => {
+        /// The year of the event.
+        const YEAR: u16 = $year;
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($year, $day);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let input = advent_of_code::template::read_file("inputs", YEAR, DAY);
+            let parsed = run_parse($parse, input.as_str(), YEAR, DAY);
+            // the closures let parts accept e.g. a slice of a parsed `Vec`.
+            run_part(|parsed| part_one(parsed), &parsed, YEAR, DAY, 1);
+            run_part(|parsed| part_two(parsed), &parsed, YEAR, DAY, 2);
+        }
+    };
+    ($year:expr, $day:expr) => {
+        /// The year of the event.
+        const YEAR: u16 = $year;
         /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        const DAY: advent_of_code::Day = advent_of_code::day!($year, $day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = advent_of_code::template::read_file("inputs", YEAR, DAY);
+            run_part(part_one, &input, YEAR, DAY, 1);
+            run_part(part_two, &input, YEAR, DAY, 2);
         }
     };
 }
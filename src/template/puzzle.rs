@@ -0,0 +1,381 @@
+/// Structure of a cached puzzle description (`data/puzzles/NN.md`).
+/// A description consists of one section per part, each made of text paragraphs and code blocks.
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::markdown::{self, decode_entities};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// A paragraph, with consecutive lines joined by spaces.
+    Text(String),
+    /// The verbatim contents of a code block, ending with a newline.
+    Code(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// The plain text of the section heading, e.g. `--- Part Two ---`.
+    pub heading: String,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub sections: Vec<Section>,
+}
+
+/// Example inputs found in a description.
+/// `part_two` is only set if part two introduces an example that differs from part one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Examples {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+lazy_static! {
+    /// An emphasized code span like `*142*`, which is how example answers are highlighted.
+    static ref EMPHASIZED_CODE: Regex = Regex::new(r"`\*((?:\\.|[^*`\\])+)\*`").unwrap();
+    static ref EMPHASIZED: Regex = Regex::new(r"(?:^|[^\\])\*((?:\\.|[^*\\])+)\*").unwrap();
+}
+
+/// Part two examples need an explicit introduction like "For example:" to be picked up.
+const MIN_PART_TWO_SCORE: u32 = 5;
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        let lines: Vec<&str> = markdown.lines().collect();
+        let mut sections: Vec<Section> = vec![];
+        let mut paragraph: Vec<&str> = vec![];
+        // lines of the open code block and whether it was opened by a fence.
+        let mut code: Option<(Vec<String>, bool)> = None;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim();
+            i += 1;
+
+            if let Some((code_lines, fenced)) = &mut code {
+                let closing = if *fenced {
+                    trimmed.starts_with("```").then_some("")
+                } else {
+                    line.split_once("</code></pre>").map(|(before, _)| before)
+                };
+
+                match closing {
+                    Some(rest) => {
+                        if !rest.is_empty() {
+                            code_lines.push(decode_entities(rest));
+                        }
+                        let block = Block::Code(join_code(code_lines));
+                        push_block(&mut sections, block);
+                        code = None;
+                    }
+                    None if *fenced => code_lines.push(line.to_string()),
+                    None => code_lines.push(decode_entities(line)),
+                }
+                continue;
+            }
+
+            if trimmed.starts_with("```") {
+                flush_paragraph(&mut sections, &mut paragraph);
+                code = Some((vec![], true));
+            } else if let Some(rest) = trimmed.strip_prefix("<pre><code>") {
+                flush_paragraph(&mut sections, &mut paragraph);
+                if let Some((inline, _)) = rest.split_once("</code></pre>") {
+                    push_block(
+                        &mut sections,
+                        Block::Code(join_code(&[decode_entities(inline)])),
+                    );
+                } else {
+                    let first = if rest.is_empty() {
+                        vec![]
+                    } else {
+                        vec![decode_entities(rest)]
+                    };
+                    code = Some((first, false));
+                }
+            } else if let Some(heading) = heading_text(trimmed, lines.get(i)) {
+                if !trimmed.starts_with('#') {
+                    // skip the underline of a setext heading.
+                    i += 1;
+                }
+                flush_paragraph(&mut sections, &mut paragraph);
+                sections.push(Section {
+                    heading: markdown::to_plain_text(heading).trim().to_string(),
+                    blocks: vec![],
+                });
+            } else if trimmed.is_empty() {
+                flush_paragraph(&mut sections, &mut paragraph);
+            } else {
+                paragraph.push(trimmed);
+            }
+        }
+
+        if let Some((code_lines, _)) = code {
+            push_block(&mut sections, Block::Code(join_code(&code_lines)));
+        }
+        flush_paragraph(&mut sections, &mut paragraph);
+
+        Self { sections }
+    }
+
+    /// The section describing a part, e.g. `1` for part one.
+    pub fn part(&self, part: u8) -> Option<&Section> {
+        self.sections.get(usize::from(part).checked_sub(1)?)
+    }
+
+    /// Finds the answer to the example of a part, i.e. the last emphasized numeric code span in its section.
+    /// Descriptions also emphasize e.g. node names, so other code spans are only used if no numeric one exists.
+    /// Falls back to the last emphasized number if the section has no emphasized code.
+    pub fn answer(&self, part: u8) -> Option<String> {
+        let texts: Vec<&str> = self
+            .part(part)?
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Text(text) => Some(text.as_str()),
+                Block::Code(_) => None,
+            })
+            .collect();
+
+        let last_match = |regex: &Regex, accept: &dyn Fn(&str) -> bool| {
+            texts
+                .iter()
+                .flat_map(|text| regex.captures_iter(text))
+                .map(|c| markdown::to_plain_text(&c[1]).trim().to_string())
+                .filter(|answer| accept(answer))
+                .last()
+        };
+
+        last_match(&EMPHASIZED_CODE, &is_number)
+            .or_else(|| last_match(&EMPHASIZED_CODE, &|answer| !answer.is_empty()))
+            .or_else(|| last_match(&EMPHASIZED, &is_number))
+    }
+
+    /// Picks the code blocks that most likely contain the example input of each part.
+    pub fn examples(&self) -> Examples {
+        let part_one = self.part(1).and_then(best_example).map(|(_, code)| code);
+
+        let part_two = self
+            .part(2)
+            .and_then(best_example)
+            .filter(|(score, code)| *score >= MIN_PART_TWO_SCORE && Some(code) != part_one.as_ref())
+            .map(|(_, code)| code);
+
+        Examples { part_one, part_two }
+    }
+}
+
+/// Scores every code block in a section by how it was introduced and returns the best one.
+fn best_example(section: &Section) -> Option<(u32, String)> {
+    let mut best: Option<(u32, String)> = None;
+    let mut intro = String::new();
+
+    for block in &section.blocks {
+        match block {
+            Block::Text(text) => intro = text.to_lowercase(),
+            Block::Code(code) => {
+                let mut score = 0;
+                if intro.contains("for example") {
+                    score += 4;
+                } else if intro.contains("example") {
+                    score += 2;
+                }
+                if intro.ends_with(':') {
+                    score += 1;
+                }
+                if code.lines().count() > 1 {
+                    score += 1;
+                }
+
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, _)| score > *best_score)
+                {
+                    best = Some((score, code.clone()));
+                }
+                intro.clear();
+            }
+        }
+    }
+
+    best
+}
+
+fn heading_text<'a>(line: &'a str, next: Option<&&str>) -> Option<&'a str> {
+    if line.starts_with('#') {
+        let text = line.trim_start_matches('#');
+        return text.starts_with(' ').then_some(text);
+    }
+
+    let underline = next?.trim();
+    let is_underline = underline.len() >= 3
+        && (underline.chars().all(|c| c == '-') || underline.chars().all(|c| c == '='));
+
+    (!line.is_empty() && is_underline).then_some(line)
+}
+
+fn push_block(sections: &mut Vec<Section>, block: Block) {
+    if sections.is_empty() {
+        sections.push(Section {
+            heading: String::new(),
+            blocks: vec![],
+        });
+    }
+    sections.last_mut().unwrap().blocks.push(block);
+}
+
+fn flush_paragraph(sections: &mut Vec<Section>, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let text = paragraph.join(" ");
+    paragraph.clear();
+    push_block(sections, Block::Text(text));
+}
+
+fn is_number(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn join_code(lines: &[String]) -> String {
+    let mut code = lines.join("\n");
+    code.push('\n');
+    code
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Block, Examples, Puzzle};
+
+    fn get_mock_puzzle() -> String {
+        [
+            "## --- Day 8: Haunted Wasteland ---",
+            "",
+            "It seems like you're meant to use the *left/right* instructions.",
+            "",
+            "```",
+            "LLR",
+            "```",
+            "",
+            "For example:",
+            "",
+            "```",
+            "RL",
+            "",
+            "AAA = (BBB, CCC)",
+            "```",
+            "",
+            "Starting with `AAA`, you need to look up the next element. Here, `*2*` steps are required.",
+            "",
+            "In total, it takes `*6*` steps to reach `*ZZZ*`. Not `2 \\* 3`.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "```",
+            "",
+            "After 2 steps:",
+            "",
+            "```",
+            "11Z",
+            "```",
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn parses_sections() {
+        let puzzle = Puzzle::parse(&get_mock_puzzle());
+
+        assert_eq!(puzzle.sections.len(), 2);
+        assert_eq!(
+            puzzle.sections[0].heading,
+            "--- Day 8: Haunted Wasteland ---"
+        );
+        assert_eq!(puzzle.sections[1].heading, "--- Part Two ---");
+        assert_eq!(puzzle.sections[0].blocks.len(), 6);
+        assert_eq!(
+            puzzle.sections[0].blocks[2],
+            Block::Text("For example:".into())
+        );
+        assert_eq!(
+            puzzle.sections[0].blocks[3],
+            Block::Code("RL\n\nAAA = (BBB, CCC)\n".into())
+        );
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let puzzle = Puzzle::parse(&get_mock_puzzle());
+        assert_eq!(puzzle.answer(1).as_deref(), Some("6"));
+        assert_eq!(puzzle.answer(2), None);
+
+        let puzzle = Puzzle::parse(
+            "## --- Day 1 ---\n\nThe sum is `*142*`.\n\n## --- Part Two ---\n\nAdding these produces *281*.\n",
+        );
+        assert_eq!(puzzle.answer(1).as_deref(), Some("142"));
+        assert_eq!(puzzle.answer(2).as_deref(), Some("281"));
+        assert_eq!(puzzle.answer(3), None);
+
+        let puzzle = Puzzle::parse("## --- Day 5 ---\n\nThe top crates are `*CMZ*`.\n");
+        assert_eq!(puzzle.answer(1).as_deref(), Some("CMZ"));
+    }
+
+    #[test]
+    fn extracts_separate_examples() {
+        let examples = Puzzle::parse(&get_mock_puzzle()).examples();
+
+        assert_eq!(
+            examples,
+            Examples {
+                part_one: Some("RL\n\nAAA = (BBB, CCC)\n".into()),
+                part_two: Some("LR\n\n11A = (11B, XXX)\n".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn reuses_part_one_example() {
+        let markdown = [
+            "## --- Day 6: Wait For It ---",
+            "",
+            "For example:",
+            "",
+            "<pre><code>Time:      7  15   30",
+            "Distance:  9  40  200",
+            "</code></pre>",
+            "",
+            "## --- Part Two ---",
+            "",
+            "So, the example from before now looks like this:",
+            "",
+            "```",
+            "Time:      71530",
+            "```",
+        ]
+        .join("\n");
+
+        let examples = Puzzle::parse(&markdown).examples();
+
+        assert_eq!(
+            examples.part_one.as_deref(),
+            Some("Time:      7  15   30\nDistance:  9  40  200\n")
+        );
+        assert_eq!(examples.part_two, None);
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        let examples = Puzzle::parse("## --- Day 1: Nothing ---\n\nNo code here.\n").examples();
+        assert_eq!(examples, Examples::default());
+    }
+}
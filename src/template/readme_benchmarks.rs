@@ -2,10 +2,18 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::{benchmark::Statistic, commands::all::get_path_for_bin, titles::Titles};
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+lazy_static! {
+    static ref BIN_LINK: Regex = Regex::new(r"\(\./src/bin/(\d{4})-\d{2}\.rs\)").unwrap();
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -21,23 +29,21 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
+    /// Time of the parse step shared by both parts, for solutions that have one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table enclosed by two occurrences of `marker`, including the markers.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,23 +64,116 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// The marker of the table of `year`, e.g. `<!--- benchmarking table 2023 --->`.
+pub fn year_marker(marker: &str, year: u16) -> String {
+    marker.replace(" --->", &format!(" {year} --->"))
+}
+
+/// Finds the table of `year`, i.e. the one enclosed by its [`year_marker`].
+/// A table enclosed by `marker` itself is used as well, unless `table_year` attributes it to another year.
+/// Returns [`None`] if the readme has tables, but none of `year`.
+pub fn locate_year_table(
+    readme: &str,
+    marker: &str,
+    year: u16,
+    table_year: impl Fn(&str) -> Option<u16>,
+) -> Result<Option<TablePosition>, Error> {
+    let own_marker = year_marker(marker, year);
+    if readme.contains(&own_marker) {
+        return locate_table(readme, &own_marker).map(Some);
+    }
+
+    if !readme.contains(marker) {
+        let prefix = marker.trim_end_matches("--->");
+        return if readme.contains(prefix) {
+            Ok(None)
+        } else {
+            locate_table(readme, marker).map(Some)
+        };
+    }
+
+    let position = locate_table(readme, marker)?;
+    match table_year(&readme[position.pos_start..position.pos_end]) {
+        Some(other) if other != year => Ok(None),
+        _ => Ok(Some(position)),
+    }
+}
+
+/// Replaces the table of `year` with `table`. Without one, `table` is added after the tables of other years.
+pub fn update_year_table(
+    s: &mut String,
+    marker: &str,
+    year: u16,
+    table_year: impl Fn(&str) -> Option<u16>,
+    table: &str,
+) -> Result<(), Error> {
+    if let Some(positions) = locate_year_table(s, marker, year, table_year)? {
+        s.replace_range(positions.pos_start..positions.pos_end, table);
+        return Ok(());
+    }
+
+    let prefix = marker.trim_end_matches("--->");
+    let end = s
+        .match_indices(prefix)
+        .last()
+        .and_then(|(pos, _)| s[pos..].find("--->").map(|end| pos + end + 4))
+        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    s.insert_str(end, &format!("\n\n{table}"));
+    Ok(())
+}
+
+/// The year of the solutions linked in a benchmark table.
+fn table_year(table: &str) -> Option<u16> {
+    BIN_LINK.captures(table)?[1].parse().ok()
+}
+
+/// Adds a title column if `titles` are given and a parse column if any solution has a parse step.
+/// Mentions `statistic` unless the table shows means.
+fn construct_table(
+    prefix: &str,
+    year: u16,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    titles: Option<&Titles>,
+    statistic: Statistic,
+) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+    let marker = year_marker(MARKER, year);
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    if statistic != Statistic::Mean {
+        lines.push(format!(
+            "Times are the {statistic} of all samples, the total is the sum of the means."
+        ));
+        lines.push(String::new());
+    }
+
+    let with_parse = timings.iter().any(|timing| timing.parse.is_some());
+    let title_header = if titles.is_some() { " Title |" } else { "" };
+    let parse_header = if with_parse { " Parse |" } else { "" };
+    let columns = 3 + usize::from(titles.is_some()) + usize::from(with_parse);
+
+    lines.push(format!(
+        "| Day |{title_header}{parse_header} Part 1 | Part 2 |"
+    ));
+    lines.push(format!("|{} :---:  |", " :---: |".repeat(columns - 1)));
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let title = titles.map(|titles| {
+            format!(
+                " {} |",
+                titles.get(timing.day).unwrap_or("-").replace('|', "\\|")
+            )
+        });
+        let parse = with_parse.then(|| format!(" `{}` |", timing.parse.as_deref().unwrap_or("-")));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{}{} `{}` | `{}` |",
             timing.day.into_inner(),
             path,
+            title.unwrap_or_default(),
+            parse.unwrap_or_default(),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
@@ -82,47 +181,62 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn update_content(
+    s: &mut String,
+    year: u16,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    titles: Option<&Titles>,
+    statistic: Statistic,
+) -> Result<(), Error> {
+    let table = construct_table("##", year, timings, total_millis, titles, statistic);
+    update_year_table(s, MARKER, year, table_year, &table)
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(
+    year: u16,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    titles: Option<&Titles>,
+    statistic: Statistic,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis, titles, statistic)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{update_content, year_marker, Timings, MARKER};
     use crate::day;
+    use crate::template::{benchmark::Statistic, titles::Titles};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -134,53 +248,193 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert_eq!(
+            s.matches(&year_marker(MARKER, 2023))
+                .collect::<Vec<&str>>()
+                .len(),
+            2
+        );
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_titles() {
+        let titles: Titles = [(day!(1), "Trebuchet?!".to_string())].into_iter().collect();
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            Some(&titles),
+            Statistic::Mean,
+        )
+        .unwrap();
+
+        assert!(s.contains("| Day | Title | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | Trebuchet?! | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | - | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_statistic() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Median,
+        )
+        .unwrap();
+        assert!(s.contains("## 2023 Benchmarks\n\nTimes are the median of all samples, the total is the sum of the means.\n\n| Day |"));
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
+        assert!(!s.contains("Times are"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, 2023, timings, 195.0, None, Statistic::Mean).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn keeps_benchmarks_of_other_years() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            2023,
+            get_mock_timings(),
+            190.0,
+            None,
+            Statistic::Mean,
+        )
+        .unwrap();
+
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        update_content(&mut s, 2022, timings.clone(), 30.0, None, Statistic::Mean).unwrap();
+        update_content(&mut s, 2022, timings, 31.0, None, Statistic::Mean).unwrap();
+
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert_eq!(s.matches("## 2022 Benchmarks").count(), 1);
+        assert!(s.contains("**Total: 190.00ms**"));
+        assert!(s.contains("| [Day 1](./src/bin/2022-01.rs) | `10ms` | `20ms` |"));
+        assert!(s.ends_with("**Total: 31.00ms**\n<!--- benchmarking table 2022 --->\nbaz"));
+    }
 }
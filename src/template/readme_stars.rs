@@ -0,0 +1,190 @@
+/// Module that updates the readme with the stars earned so far.
+/// Produces the same table as the `advent-readme-stars` action, but from local state.
+use std::fs;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::template::{
+    leaderboard::Member,
+    readme_benchmarks::{locate_year_table, update_year_table, year_marker, Error},
+    submissions::Ledger,
+};
+use crate::{all_days_of, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+lazy_static! {
+    static ref RESULTS_HEADER: Regex = Regex::new(r"(\d{4}) Results").unwrap();
+}
+
+/// Days with at least one star and the number of stars earned on them.
+pub type Stars = Vec<(Day, usize)>;
+
+/// Stars for the parts that have a correct answer in the ledger.
+pub fn from_ledger(ledger: &Ledger, year: u16) -> Stars {
+    collect(year, |day| {
+        (1..=2)
+            .filter(|part| ledger.is_solved(year, day, *part))
+            .count()
+    })
+}
+
+/// Stars of a leaderboard member in `year`.
+pub fn from_member(member: &Member, year: u16) -> Stars {
+    collect(year, |day| member.stars_on(day))
+}
+
+/// Combines two sets of stars, keeping the higher count for every day.
+pub fn merge(a: &Stars, b: &Stars) -> Stars {
+    let stars_in = |stars: &Stars, day: Day| {
+        stars
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map_or(0, |(_, count)| *count)
+    };
+    let mut merged: Stars = a
+        .iter()
+        .chain(b)
+        .map(|(day, _)| (*day, stars_in(a, *day).max(stars_in(b, *day))))
+        .collect();
+    merged.sort();
+    merged.dedup();
+    merged
+}
+
+fn collect(year: u16, stars_on: impl Fn(Day) -> usize) -> Stars {
+    all_days_of(year)
+        .map(|day| (day, stars_on(day)))
+        .filter(|(_, stars)| *stars > 0)
+        .collect()
+}
+
+/// Reads the stars listed in the table of `year` in `readme`.
+pub fn read(readme: &str, year: u16) -> Result<Stars, Error> {
+    let positions = locate_year_table(readme, MARKER, year, table_year)?
+        .ok_or_else(|| Error::Parser(format!("Could not find the table of {year}.")))?;
+
+    Ok(readme[positions.pos_start..positions.pos_end]
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let day = cells.get(1)?.strip_prefix("[Day ")?.split(']').next()?;
+            let day = Day::new(day.parse().ok()?)?;
+            let stars = cells.iter().skip(2).filter(|c| c.contains('⭐')).count();
+            Some((day, stars))
+        })
+        .filter(|(_, stars)| *stars > 0)
+        .collect())
+}
+
+/// The year in the header of a stars table.
+fn table_year(table: &str) -> Option<u16> {
+    RESULTS_HEADER.captures(table)?[1].parse().ok()
+}
+
+fn construct_table(prefix: &str, year: u16, stars: &Stars) -> String {
+    let header = format!("{prefix} {year} Results");
+    let marker = year_marker(MARKER, year);
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, count) in stars {
+        let star = |part| if *count >= part { "⭐" } else { " " };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(1),
+            star(2)
+        ));
+    }
+
+    lines.push(marker);
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, stars: &Stars) -> Result<(), Error> {
+    let table = construct_table("##", year, stars);
+    update_year_table(s, MARKER, year, table_year, &table)
+}
+
+pub fn update(year: u16, stars: &Stars) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{merge, read, update_content, Stars, MARKER};
+    use crate::day;
+
+    fn get_mock_stars() -> Stars {
+        vec![(day!(1), 2), (day!(7), 1)]
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &get_mock_stars()).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n## old\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, 2023, &get_mock_stars()).unwrap();
+        update_content(&mut s, 2023, &get_mock_stars()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table 2023 --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 7](https://adventofcode.com/2023/day/7) | ⭐ |   |",
+            "<!--- advent_readme_stars table 2023 --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+        assert_eq!(read(&s, 2023).unwrap(), get_mock_stars());
+    }
+
+    #[test]
+    fn merges_stars_by_day() {
+        let listed = vec![(day!(1), 2), (day!(3), 1)];
+        let solved = vec![(day!(1), 1), (day!(2), 1), (day!(3), 2)];
+        assert_eq!(
+            merge(&listed, &solved),
+            vec![(day!(1), 2), (day!(2), 1), (day!(3), 2)]
+        );
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let mut s = format!("foo\n{}\n## 2023 Results\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, 2022, &vec![(day!(3), 2)]).unwrap();
+        update_content(&mut s, 2023, &get_mock_stars()).unwrap();
+        update_content(&mut s, 2022, &vec![(day!(3), 1)]).unwrap();
+
+        assert!(s.starts_with("foo\n<!--- advent_readme_stars table 2023 --->\n## 2023 Results"));
+        assert!(s.ends_with("<!--- advent_readme_stars table 2022 --->\nbaz"));
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+        assert_eq!(s.matches("## 2022 Results").count(), 1);
+        assert_eq!(read(&s, 2023).unwrap(), get_mock_stars());
+        assert_eq!(read(&s, 2022).unwrap(), vec![(day!(3), 1)]);
+        assert!(read(&s, 2021).is_err());
+    }
+}
@@ -1,63 +1,119 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::bench_history::{self, History};
+use crate::template::benchmark::{BenchConfig, BenchOverrides, Summary};
+use crate::template::cooldown::{self, Cooldowns};
+use crate::template::submissions::{self, Ledger, Submission};
+use crate::template::timeline::{self, Event};
+use crate::template::verdict::Verdict;
+use crate::template::{aoc_client, countdown, format_remaining, ANSI_ITALIC, ANSI_RESET};
 use crate::Day;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
+
+use chrono::Utc;
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    year: u16,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let bench_config = get_bench_config();
+
+    let (result, summary) = run_timed(func, input, bench_config.as_ref(), |result| {
+        print_result(result, &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_summary(&summary));
+
+    if bench_config.is_some() && result.is_some() {
+        record_benchmark(year, day, part, summary);
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, year, day, part);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Runs the parse step whose output is shared by both parts, and reports its time like the time of a part.
+/// Its benchmarks are recorded as part `0`.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, year: u16, day: Day) -> T {
+    let bench_config = get_bench_config();
+
+    let (parsed, summary) = run_timed(func, input, bench_config.as_ref(), |_| {
+        print!("Parse:");
+    });
+
+    print!("\r");
+    println!("Parse:{}", format_summary(&summary));
+
+    if bench_config.is_some() {
+        record_benchmark(year, day, 0, summary);
+    }
+
+    parsed
+}
+
+/// The benchmark settings if `--time` was passed.
+fn get_bench_config() -> Option<BenchConfig> {
+    let args: Vec<String> = env::args().collect();
+    args.iter().any(|x| x == "--time").then(|| {
+        BenchOverrides::from_args(&args)
+            .and_then(|overrides| BenchConfig::from_env()?.apply(&overrides))
+            .unwrap_or_else(|e| {
+                eprintln!("Invalid benchmark settings: {e}");
+                process::exit(1);
+            })
+    })
+}
+
+/// Run a solution part. The behavior differs depending on whether a `bench_config` is passed (`--time`):
+///  1. without, the function is executed once.
+///  2. with, the function is benched (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<&BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Summary) {
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let samples = match bench_config {
+        Some(config) => bench(func, input, &base_time, config),
+        None => vec![base_time],
     };
 
-    (result, run.0, run.1)
+    // there is always at least one sample.
+    (result, Summary::from_samples(&samples).unwrap())
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+) -> Vec<Duration> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..config.warmup {
+        func(input.clone());
+    }
+
+    let bench_iterations = config.samples(*base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,26 +125,20 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    timers
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// The mean and sample count, followed by a line with the spread of the samples if there is more than one.
+fn format_summary(summary: &Summary) -> String {
+    if summary.samples == 1 {
+        format!(" ({:.1?})", summary.mean)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} @ {} samples)\n    {}",
+            summary.mean,
+            summary.samples,
+            summary.format_spread()
+        )
     }
 }
 
@@ -129,12 +179,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///
+/// If a submission cooldown is active, the answer is only submitted once it elapsed and `--wait` was passed.
 fn submit_result<T: Display>(
     result: T,
+    year: u16,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +210,135 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_client::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    let mut ledger = match Ledger::load(submissions::LEDGER_PATH) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("failed to load submission ledger: {e:?}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(previous) = ledger.find_rejected(year, day, part, &answer) {
+        eprintln!(
+            "Answer {answer} was already rejected on {}. Not submitting it again.",
+            previous.format_time()
+        );
+        return None;
+    }
+
+    if let Some(bound) = ledger.find_out_of_bounds(year, day, part, &answer) {
+        eprintln!(
+            "Answer {answer} is ruled out by {} ({}). Not submitting it.",
+            bound.answer, bound.verdict
+        );
+        return None;
+    }
+
+    print_history(&ledger.history(year, day, part));
+
+    let should_wait = args.contains(&"--wait".into());
+
+    let mut cooldowns = match Cooldowns::load(cooldown::COOLDOWN_PATH) {
+        Ok(cooldowns) => cooldowns,
+        Err(e) => {
+            eprintln!("failed to load submission cooldowns: {e:?}");
+            process::exit(1);
+        }
+    };
+
+    loop {
+        if let Some(remaining) = cooldowns.remaining(Utc::now().timestamp()) {
+            if !should_wait {
+                eprintln!(
+                    "Submission cooldown active for another {}. Pass --wait to submit once it elapsed.",
+                    format_remaining(&remaining)
+                );
+                return None;
+            }
+            countdown("Waiting for submission cooldown:", remaining);
+        }
+
+        println!("Submitting result...");
+        let response = aoc_client::submit(year, day, part, &answer);
+
+        match &response {
+            Ok(verdict) => {
+                println!("{ANSI_BOLD}Verdict:{ANSI_RESET} {verdict}");
+                let submission = Submission::new(year, day, part, &answer, verdict.clone());
+                if let Err(e) = ledger.record(submission) {
+                    eprintln!("failed to record submission: {e:?}");
+                }
+                record_milestones(year, day, part, verdict);
+
+                if let Verdict::TooRecent(Some(wait)) = verdict {
+                    let deadline = i64::try_from(wait.as_secs())
+                        .map_or(i64::MAX, |wait| Utc::now().timestamp().saturating_add(wait));
+                    if let Err(e) = cooldowns.set(deadline) {
+                        eprintln!("failed to store submission cooldown: {e:?}");
+                    }
+                    if should_wait {
+                        continue;
+                    }
+                }
+            }
+            Err(e) => eprintln!("failed to submit result: {e}"),
+        }
+
+        return Some(response);
+    }
+}
+
+/// Appends the benchmark to the history, so later runs can be compared with it.
+fn record_benchmark(year: u16, day: Day, part: u8, summary: Summary) {
+    let entry = bench_history::Entry {
+        year,
+        day,
+        part,
+        timestamp: Utc::now().timestamp(),
+        commit: bench_history::current_commit(),
+        release: !cfg!(debug_assertions),
+        summary,
+    };
+
+    if let Err(e) =
+        History::load(bench_history::BENCH_HISTORY_PATH).and_then(|mut h| h.record(entry))
+    {
+        eprintln!("failed to record benchmark: {e:?}");
+    }
+}
+
+fn record_milestones(year: u16, day: Day, part: u8, verdict: &Verdict) {
+    let mut events = vec![Event::Submitted(part)];
+    if *verdict == Verdict::Correct {
+        events.push(Event::Solved(part));
+    }
+
+    for event in events {
+        if let Err(e) = timeline::record_now(year, day, event) {
+            eprintln!("failed to record {event} time: {e:?}");
+        }
+    }
+}
+
+fn print_history(history: &[&Submission]) {
+    if history.is_empty() {
+        return;
+    }
+
+    println!("{ANSI_BOLD}Previous submissions:{ANSI_RESET}");
+    for submission in history {
+        println!(
+            "  {} {ANSI_ITALIC}{}{ANSI_RESET} ({})",
+            submission.answer,
+            submission.verdict,
+            submission.format_time()
+        );
+    }
 }
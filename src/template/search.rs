@@ -0,0 +1,312 @@
+/// Offline full-text search across the puzzle descriptions cached in `data/puzzles`.
+use std::{collections::HashMap, fs, ops::Range};
+
+use crate::template::{
+    aoc_client, markdown, markdown::RenderMode, titles::Titles, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days_of, Day};
+
+/// Snippets are cut to roughly this many characters around the first match.
+const SNIPPET_WIDTH: usize = 100;
+const MAX_SNIPPETS: usize = 2;
+/// Matches in the title count this many times as much as matches in the text.
+const TITLE_WEIGHT: f64 = 3.0;
+
+struct Document {
+    day: Day,
+    title: String,
+    /// Plain text lines of the description.
+    lines: Vec<String>,
+    /// Number of occurrences of every word.
+    words: HashMap<String, usize>,
+}
+
+pub struct Index {
+    documents: Vec<Document>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub day: Day,
+    pub title: String,
+    pub score: f64,
+    pub snippets: Vec<Snippet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub text: String,
+    /// Byte ranges of the matched words in `text`.
+    pub matches: Vec<Range<usize>>,
+}
+
+impl Index {
+    /// Indexes the descriptions of every day with a cached description.
+    pub fn load(year: u16) -> Self {
+        let titles = Titles::load(year);
+        Self::build(all_days_of(year).filter_map(|day| {
+            let markdown = fs::read_to_string(aoc_client::get_puzzle_path(year, day)).ok()?;
+            Some((
+                day,
+                titles.get(day).unwrap_or_default().to_string(),
+                markdown,
+            ))
+        }))
+    }
+
+    pub fn build(descriptions: impl IntoIterator<Item = (Day, String, String)>) -> Self {
+        let documents = descriptions
+            .into_iter()
+            .map(|(day, title, markdown)| {
+                let lines: Vec<String> = markdown
+                    .lines()
+                    .map(|line| {
+                        markdown::to_plain_text(line.trim_start_matches('#'))
+                            .trim()
+                            .to_string()
+                    })
+                    .filter(|line| !line.is_empty())
+                    .collect();
+
+                let mut words = HashMap::new();
+                for line in &lines {
+                    for (_, word) in tokenize(line) {
+                        *words.entry(word).or_insert(0) += 1;
+                    }
+                }
+
+                Document {
+                    day,
+                    title,
+                    lines,
+                    words,
+                }
+            })
+            .collect();
+
+        Self { documents }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Ranks descriptions by how many of the query terms they contain, then by a tf-idf score.
+    /// Terms match words they are a prefix of, so `hailstone` matches `hailstones`.
+    pub fn search(&self, query: &str) -> Vec<Match> {
+        let terms: Vec<String> = tokenize(query).into_iter().map(|(_, t)| t).collect();
+        if terms.is_empty() {
+            return vec![];
+        }
+
+        let count = |document: &Document, term: &str| -> usize {
+            document
+                .words
+                .iter()
+                .filter(|(word, _)| word.starts_with(term))
+                .map(|(_, n)| n)
+                .sum()
+        };
+
+        let mut matches: Vec<(usize, Match)> = self
+            .documents
+            .iter()
+            .filter_map(|document| {
+                let mut matched_terms = 0;
+                let mut score = 0.0;
+
+                for term in &terms {
+                    let in_text = count(document, term);
+                    let in_title = tokenize(&document.title)
+                        .iter()
+                        .filter(|(_, word)| word.starts_with(term.as_str()))
+                        .count();
+                    if in_text + in_title == 0 {
+                        continue;
+                    }
+
+                    let frequency = self.documents.iter().filter(|d| count(d, term) > 0).count();
+                    #[allow(clippy::cast_precision_loss)]
+                    let idf = (1.0 + self.documents.len() as f64 / frequency.max(1) as f64).ln();
+                    #[allow(clippy::cast_precision_loss)]
+                    let weight = (1.0 + in_text as f64).ln() + TITLE_WEIGHT * in_title as f64;
+
+                    matched_terms += 1;
+                    score += weight * idf;
+                }
+
+                (matched_terms > 0).then(|| {
+                    let found = Match {
+                        day: document.day,
+                        title: document.title.clone(),
+                        score,
+                        snippets: snippets(document, &terms),
+                    };
+                    (matched_terms, found)
+                })
+            })
+            .collect();
+
+        matches.sort_by(|(terms_a, a), (terms_b, b)| {
+            terms_b
+                .cmp(terms_a)
+                .then(b.score.total_cmp(&a.score))
+                .then(a.day.cmp(&b.day))
+        });
+
+        matches.into_iter().map(|(_, m)| m).collect()
+    }
+}
+
+impl Snippet {
+    pub fn highlight(&self, mode: RenderMode) -> String {
+        let (open, close) = match mode {
+            RenderMode::Ansi => (ANSI_BOLD, ANSI_RESET),
+            RenderMode::Plain => ("**", "**"),
+        };
+
+        let mut out = String::new();
+        let mut pos = 0;
+        for range in &self.matches {
+            out.push_str(&self.text[pos..range.start]);
+            out.push_str(open);
+            out.push_str(&self.text[range.clone()]);
+            out.push_str(close);
+            pos = range.end;
+        }
+        out.push_str(&self.text[pos..]);
+        out
+    }
+}
+
+/// The lines with the most matched terms, cut around their first match.
+fn snippets(document: &Document, terms: &[String]) -> Vec<Snippet> {
+    let mut scored: Vec<(usize, usize, Vec<Range<usize>>)> = document
+        .lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let matches: Vec<Range<usize>> = tokenize(line)
+                .into_iter()
+                .filter(|(_, word)| terms.iter().any(|t| word.starts_with(t.as_str())))
+                .map(|(range, _)| range)
+                .collect();
+            (!matches.is_empty()).then_some((matches.len(), i, matches))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    scored
+        .into_iter()
+        .take(MAX_SNIPPETS)
+        .map(|(_, i, matches)| cut(&document.lines[i], &matches))
+        .collect()
+}
+
+/// Shortens a line to about [`SNIPPET_WIDTH`] characters around the first match.
+fn cut(line: &str, matches: &[Range<usize>]) -> Snippet {
+    let first = matches[0].start;
+    let mut start = first.saturating_sub(SNIPPET_WIDTH / 3);
+    while !line.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (start + SNIPPET_WIDTH).min(line.len());
+    while !line.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < line.len() { "…" } else { "" };
+    let offset = prefix.len();
+
+    Snippet {
+        text: format!("{prefix}{}{suffix}", &line[start..end]),
+        matches: matches
+            .iter()
+            .filter(|m| m.start >= start && m.end <= end)
+            .map(|m| m.start - start + offset..m.end - start + offset)
+            .collect(),
+    }
+}
+
+/// Splits text into lowercase words and their byte ranges.
+fn tokenize(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = vec![];
+    let mut start = None;
+
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s..i, text[s..i].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    words
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{tokenize, Index};
+    use crate::day;
+    use crate::template::markdown::RenderMode;
+
+    fn get_mock_index() -> Index {
+        Index::build([
+            (
+                day!(8),
+                "Haunted Wasteland".to_string(),
+                "## --- Day 8: Haunted Wasteland ---\n\nFind the *LCM* of all cycle lengths.\n\n```\nAAA = (BBB, CCC)\n```\n".to_string(),
+            ),
+            (
+                day!(24),
+                "Never Tell Me The Odds".to_string(),
+                "## --- Day 24: Never Tell Me The Odds ---\n\nThe hailstones fly. Each hailstone has a position.\n\nOdds are the hailstones collide.\n".to_string(),
+            ),
+            (
+                day!(9),
+                "Mirage Maintenance".to_string(),
+                "## --- Day 9: Mirage Maintenance ---\n\nA hailstone is not mentioned here, but the lcm is.\n".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn tokenizes_words() {
+        let words: Vec<String> = tokenize("Find the `LCM`, twice!")
+            .into_iter()
+            .map(|(_, w)| w)
+            .collect();
+        assert_eq!(words, vec!["find", "the", "lcm", "twice"]);
+    }
+
+    #[test]
+    fn ranks_matches() {
+        let index = get_mock_index();
+
+        let days: Vec<_> = index.search("hailstone").iter().map(|m| m.day).collect();
+        assert_eq!(days, vec![day!(24), day!(9)]);
+
+        let days: Vec<_> = index.search("LCM cycle").iter().map(|m| m.day).collect();
+        assert_eq!(days, vec![day!(8), day!(9)]);
+
+        assert!(index.search("camel").is_empty());
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn highlights_snippets() {
+        let matches = get_mock_index().search("lcm");
+        let snippet = &matches[0].snippets[0];
+
+        assert_eq!(snippet.text, "Find the LCM of all cycle lengths.");
+        assert_eq!(
+            snippet.highlight(RenderMode::Plain),
+            "Find the **LCM** of all cycle lengths."
+        );
+    }
+}
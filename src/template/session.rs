@@ -0,0 +1,232 @@
+/// Lookup and storage of the Advent of Code session token.
+///
+/// The token is resolved in this order:
+///  1. the `AOC_SESSION` environment variable.
+///  2. the config file `$XDG_CONFIG_HOME/adventofcode.session` (defaults to `~/.config/adventofcode.session`).
+///  3. `~/.adventofcode.session`, the location aoc-cli uses.
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Session cookies are valid for about a month.
+pub const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionSource {
+    Env,
+    ConfigFile(PathBuf),
+    HomeFile(PathBuf),
+}
+
+impl SessionSource {
+    /// The file the token was read from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            SessionSource::Env => None,
+            SessionSource::ConfigFile(path) | SessionSource::HomeFile(path) => Some(path),
+        }
+    }
+}
+
+impl Display for SessionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionSource::Env => write!(f, "environment variable AOC_SESSION"),
+            SessionSource::ConfigFile(path) | SessionSource::HomeFile(path) => {
+                write!(f, "\"{}\"", path.display())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub token: String,
+    pub source: SessionSource,
+    /// When the token file was last written. Unknown for tokens from the environment.
+    pub modified: Option<SystemTime>,
+}
+
+impl Session {
+    /// Shows the start and end of the token only.
+    pub fn masked(&self) -> String {
+        mask(&self.token)
+    }
+
+    /// Whether the token was stored longer ago than a session usually lives.
+    pub fn is_probably_expired(&self, now: SystemTime) -> bool {
+        self.modified
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > SESSION_LIFETIME)
+    }
+}
+
+/// Resolves the session token from the environment and the known token files.
+pub fn load() -> Option<Session> {
+    load_from(env::var("AOC_SESSION").ok(), config_path(), home_path())
+}
+
+fn load_from(
+    env_token: Option<String>,
+    config_path: Option<PathBuf>,
+    home_path: Option<PathBuf>,
+) -> Option<Session> {
+    if let Some(token) = env_token.filter(|t| !t.trim().is_empty()) {
+        return Some(Session {
+            token: token.trim().into(),
+            source: SessionSource::Env,
+            modified: None,
+        });
+    }
+
+    let candidates = [
+        config_path.map(SessionSource::ConfigFile),
+        home_path.map(SessionSource::HomeFile),
+    ];
+
+    candidates.into_iter().flatten().find_map(|source| {
+        let path = source.path()?;
+        let token = fs::read_to_string(path).ok()?;
+        let token = token.trim();
+        if token.is_empty() {
+            return None;
+        }
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Some(Session {
+            token: token.into(),
+            source,
+            modified,
+        })
+    })
+}
+
+/// Writes the token to the config file and returns its path.
+pub fn store(token: &str) -> io::Result<PathBuf> {
+    let path = config_path().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "could not determine home directory",
+        )
+    })?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_token(&path, token)?;
+    Ok(path)
+}
+
+/// The token grants access to the account, so the file is only readable by its owner on unix.
+fn write_token(path: &Path, token: &str) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to new files.
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", token.trim())
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))?;
+    Some(config_dir.join("adventofcode.session"))
+}
+
+pub fn home_path() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".adventofcode.session"))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn mask(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{start}{}{end}", "*".repeat(chars.len() - 8))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_from, mask, write_token, SessionSource, SESSION_LIFETIME};
+    use std::{env, fs, time::SystemTime};
+
+    #[test]
+    fn masks_tokens() {
+        assert_eq!(mask("53616c7465645f5f"), "5361********5f5f");
+        assert_eq!(mask("short"), "*****");
+    }
+
+    #[test]
+    fn resolves_tokens_in_order() {
+        let dir = env::temp_dir().join(format!("aoc-session-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.session");
+        let home = dir.join("home.session");
+        fs::write(&home, "home-token\n").unwrap();
+
+        let session = load_from(
+            Some("env-token".into()),
+            Some(config.clone()),
+            Some(home.clone()),
+        )
+        .unwrap();
+        assert_eq!(session.token, "env-token");
+        assert_eq!(session.source, SessionSource::Env);
+
+        let session =
+            load_from(Some("  ".into()), Some(config.clone()), Some(home.clone())).unwrap();
+        assert_eq!(session.token, "home-token");
+        assert_eq!(session.source, SessionSource::HomeFile(home.clone()));
+
+        fs::write(&config, "config-token").unwrap();
+        let session = load_from(None, Some(config.clone()), Some(home.clone())).unwrap();
+        assert_eq!(session.token, "config-token");
+        assert_eq!(session.source, SessionSource::ConfigFile(config.clone()));
+        assert!(!session.is_probably_expired(SystemTime::now()));
+        assert!(session.is_probably_expired(SystemTime::now() + SESSION_LIFETIME * 2));
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(load_from(None, Some(config), Some(home)).is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stores_token_privately() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join(format!("aoc-session-store-{}", std::process::id()));
+        fs::write(&path, "old-token-that-is-longer\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_token(&path, " new-token ").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new-token\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
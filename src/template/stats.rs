@@ -0,0 +1,133 @@
+/// Personal completion statistics derived from the star timestamps of a leaderboard member.
+use std::time::Duration;
+
+use crate::template::{leaderboard::Member, unlock};
+use crate::{all_days_of, Day};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: Day,
+    /// Time from the unlock to the first star.
+    pub part_one: Option<Duration>,
+    /// Time from the unlock to the second star.
+    pub part_two: Option<Duration>,
+}
+
+impl DayStats {
+    /// Time between the first and the second star.
+    pub fn gap(&self) -> Option<Duration> {
+        Some(self.part_two?.saturating_sub(self.part_one?))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.part_two.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Streaks {
+    /// Most consecutive days with both stars.
+    pub longest: usize,
+    /// Most consecutive days with both stars earned within a day of the unlock.
+    pub same_day: usize,
+}
+
+/// Stats for every day `member` earned at least one star on.
+pub fn day_stats(member: &Member, year: u16) -> Vec<DayStats> {
+    all_days_of(year)
+        .filter(|day| member.stars_on(*day) > 0)
+        .map(|day| {
+            let unlock = unlock::unlock_time(year, day).timestamp();
+            let since_unlock = |part| {
+                let ts = member.star_ts(day, part)?;
+                Some(Duration::from_secs(u64::try_from(ts - unlock).unwrap_or(0)))
+            };
+
+            DayStats {
+                day,
+                part_one: since_unlock(1),
+                part_two: since_unlock(2),
+            }
+        })
+        .collect()
+}
+
+pub fn streaks(stats: &[DayStats]) -> Streaks {
+    const ONE_DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    let longest_run = |counts: &dyn Fn(&DayStats) -> bool| {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous: Option<Day> = None;
+
+        for day in stats.iter().filter(|s| counts(s)).map(|s| s.day) {
+            let consecutive = previous.is_some_and(|p| p.into_inner() + 1 == day.into_inner());
+            current = if consecutive { current + 1 } else { 1 };
+            longest = longest.max(current);
+            previous = Some(day);
+        }
+
+        longest
+    };
+
+    Streaks {
+        longest: longest_run(&DayStats::is_complete),
+        same_day: longest_run(&|s| s.part_two.is_some_and(|t| t < ONE_DAY)),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{day_stats, streaks, DayStats, Streaks};
+    use crate::day;
+    use crate::template::leaderboard::Leaderboard;
+    use std::time::Duration;
+
+    fn get_mock_member_json() -> String {
+        // 2023-12-01 05:00 UTC is 1701406800.
+        r#"{"event": "2023", "members": {"1": {
+            "id": 1, "name": "Jane Doe", "stars": 5, "local_score": 10,
+            "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1701407400}, "2": {"get_star_ts": 1701408000}},
+                "2": {"1": {"get_star_ts": 1701500000}, "2": {"get_star_ts": 1701600000}},
+                "3": {"1": {"get_star_ts": 1701700000}}
+            }
+        }}}"#
+            .into()
+    }
+
+    #[test]
+    fn measures_time_from_unlock() {
+        let leaderboard = Leaderboard::parse(&get_mock_member_json()).unwrap();
+        let stats = day_stats(&leaderboard.members["1"], 2023);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(
+            stats[0],
+            DayStats {
+                day: day!(1),
+                part_one: Some(Duration::from_secs(600)),
+                part_two: Some(Duration::from_secs(1200)),
+            }
+        );
+        assert_eq!(stats[0].gap(), Some(Duration::from_secs(600)));
+        assert_eq!(stats[2].part_two, None);
+        assert_eq!(stats[2].gap(), None);
+    }
+
+    #[test]
+    fn counts_streaks() {
+        let leaderboard = Leaderboard::parse(&get_mock_member_json()).unwrap();
+        let stats = day_stats(&leaderboard.members["1"], 2023);
+
+        // day 2 was finished more than a day after its unlock.
+        assert_eq!(
+            streaks(&stats),
+            Streaks {
+                longest: 2,
+                same_day: 1
+            }
+        );
+        assert_eq!(streaks(&[]), Streaks::default());
+    }
+}
@@ -0,0 +1,125 @@
+/// Per-day overview of the files, test results and stars of the project.
+use crate::template::{aoc_client::get_input_path, commands::all::get_path_for_bin};
+use crate::Day;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayStatus {
+    pub has_bin: bool,
+    pub has_example: bool,
+    pub has_input: bool,
+    pub has_puzzle: bool,
+    /// Whether the tests of the day pass. [`None`] if they were not run.
+    pub tests_pass: Option<bool>,
+    /// Stars listed in the README.
+    pub stars: usize,
+    /// Parts with a correct answer in the submission ledger.
+    pub solved_parts: usize,
+    /// Whether each part returned a result for the real input. [`None`] if the solution was not run.
+    pub returns: Option<[bool; 2]>,
+}
+
+impl DayStatus {
+    /// Describes facts that contradict each other, e.g. stars for a part that returns `None`.
+    pub fn inconsistencies(&self, year: u16, day: Day) -> Vec<String> {
+        let mut issues = vec![];
+
+        if self.stars > 0 && !self.has_bin {
+            issues.push(format!(
+                "Day {day}: README lists {} stars but {} is missing.",
+                self.stars,
+                get_path_for_bin(year, day)
+            ));
+        }
+
+        if self.has_bin && !self.has_input {
+            issues.push(format!(
+                "Day {day}: the solution exists but {} is missing or empty.",
+                get_input_path(year, day)
+            ));
+        }
+
+        if let Some(returns) = self.returns {
+            for (part, (name, returns)) in ["part_one", "part_two"].iter().zip(returns).enumerate()
+            {
+                if self.stars > part && !returns {
+                    issues.push(format!(
+                        "Day {day}: README lists {} stars but {name} returns None.",
+                        self.stars
+                    ));
+                }
+            }
+        }
+
+        if self.solved_parts > self.stars {
+            issues.push(format!(
+                "Day {day}: the ledger has {} correct answers but README lists {} stars. Run `cargo stars` to update it.",
+                self.solved_parts, self.stars
+            ));
+        }
+
+        issues
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DayStatus;
+    use crate::day;
+
+    fn get_mock_status() -> DayStatus {
+        DayStatus {
+            has_bin: true,
+            has_example: true,
+            has_input: true,
+            has_puzzle: true,
+            tests_pass: Some(true),
+            stars: 2,
+            solved_parts: 2,
+            returns: Some([true, true]),
+        }
+    }
+
+    #[test]
+    fn accepts_consistent_days() {
+        assert!(get_mock_status().inconsistencies(2023, day!(1)).is_empty());
+        assert!(DayStatus::default()
+            .inconsistencies(2023, day!(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn flags_inconsistencies() {
+        let status = DayStatus {
+            returns: Some([true, false]),
+            solved_parts: 1,
+            stars: 1,
+            ..get_mock_status()
+        };
+        assert!(status.inconsistencies(2023, day!(7)).is_empty());
+
+        let status = DayStatus {
+            returns: Some([true, false]),
+            ..get_mock_status()
+        };
+        assert_eq!(
+            status.inconsistencies(2023, day!(7)),
+            vec!["Day 07: README lists 2 stars but part_two returns None."]
+        );
+
+        let status = DayStatus {
+            has_bin: false,
+            has_input: false,
+            returns: None,
+            solved_parts: 2,
+            stars: 1,
+            ..get_mock_status()
+        };
+        assert_eq!(
+            status.inconsistencies(2023, day!(3)),
+            vec![
+                "Day 03: README lists 1 stars but ./src/bin/2023-03.rs is missing.",
+                "Day 03: the ledger has 2 correct answers but README lists 1 stars. Run `cargo stars` to update it.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+/// Local ledger of submitted answers.
+/// Every submission is appended to a tab-separated file so answers that were rejected are never sent twice.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, TimeZone, Utc};
+
+use crate::template::verdict::Verdict;
+use crate::Day;
+
+pub const LEDGER_PATH: &str = "data/submissions.tsv";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix timestamp (seconds) of the submission.
+    pub timestamp: i64,
+}
+
+impl Submission {
+    pub fn new(year: u16, day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: Utc::now().timestamp(),
+        }
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.verdict.is_rejected()
+    }
+
+    /// Formats the submission time in the local timezone.
+    pub fn format_time(&self) -> String {
+        match Local.timestamp_opt(self.timestamp, 0).single() {
+            Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => self.timestamp.to_string(),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            escape(&self.answer),
+            self.verdict.key(),
+            self.timestamp
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Self, Error> {
+        let parse_err = || Error::Parser(format!("malformed ledger line: {line}"));
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        let [year, day, part, answer, verdict, timestamp] = fields[..] else {
+            return Err(parse_err());
+        };
+
+        Ok(Self {
+            year: year.parse().map_err(|_| parse_err())?,
+            day: day.parse().map_err(|_| parse_err())?,
+            part: part.parse().map_err(|_| parse_err())?,
+            answer: unescape(answer),
+            verdict: verdict.parse().map_err(|_| parse_err())?,
+            timestamp: timestamp.parse().map_err(|_| parse_err())?,
+        })
+    }
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Loads the ledger at `path`. A missing file is treated as an empty ledger.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    /// All previous submissions for a part, oldest first.
+    pub fn history(&self, year: u16, day: Day, part: u8) -> Vec<&Submission> {
+        self.entries
+            .iter()
+            .filter(|s| s.year == year && s.day == day && s.part == part)
+            .collect()
+    }
+
+    /// Whether a correct answer was recorded for a part.
+    /// [`Verdict::AlreadySolved`] does not count, the site also sends it for a part that is not unlocked yet.
+    pub fn is_solved(&self, year: u16, day: Day, part: u8) -> bool {
+        self.history(year, day, part)
+            .iter()
+            .any(|s| s.verdict == Verdict::Correct)
+    }
+
+    /// Returns the earlier submission if `answer` was already rejected for this part.
+    pub fn find_rejected(
+        &self,
+        year: u16,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Option<&Submission> {
+        self.history(year, day, part)
+            .into_iter()
+            .find(|s| s.answer == answer && s.is_rejected())
+    }
+
+    /// Returns an earlier "too high" / "too low" submission that already rules out a numeric `answer`.
+    pub fn find_out_of_bounds(
+        &self,
+        year: u16,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Option<&Submission> {
+        let value: i128 = answer.trim().parse().ok()?;
+
+        self.history(year, day, part).into_iter().find(|s| {
+            let Ok(bound) = s.answer.trim().parse::<i128>() else {
+                return false;
+            };
+            match s.verdict {
+                Verdict::TooHigh => value >= bound,
+                Verdict::TooLow => value <= bound,
+                _ => false,
+            }
+        })
+    }
+
+    /// Appends a submission to the ledger file.
+    pub fn record(&mut self, submission: Submission) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(file, "{}", submission.to_line())?;
+        self.entries.push(submission);
+        Ok(())
+    }
+}
+
+fn parse(contents: &str) -> Result<Vec<Submission>, Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(Submission::from_line)
+        .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Ledger, Submission};
+    use crate::day;
+    use crate::template::verdict::Verdict;
+
+    fn get_mock_ledger() -> Ledger {
+        Ledger {
+            path: "unused.tsv".into(),
+            entries: vec![
+                Submission {
+                    year: 2023,
+                    day: day!(7),
+                    part: 1,
+                    answer: "250120186".into(),
+                    verdict: Verdict::Incorrect,
+                    timestamp: 1_701_936_000,
+                },
+                Submission {
+                    year: 2023,
+                    day: day!(7),
+                    part: 1,
+                    answer: "250254244".into(),
+                    verdict: Verdict::Correct,
+                    timestamp: 1_701_936_100,
+                },
+                Submission {
+                    year: 2022,
+                    day: day!(7),
+                    part: 1,
+                    answer: "42".into(),
+                    verdict: Verdict::Incorrect,
+                    timestamp: 1_670_400_000,
+                },
+                Submission {
+                    year: 2023,
+                    day: day!(7),
+                    part: 2,
+                    answer: "1000".into(),
+                    verdict: Verdict::TooLow,
+                    timestamp: 1_701_937_000,
+                },
+                Submission {
+                    year: 2023,
+                    day: day!(7),
+                    part: 2,
+                    answer: "5000".into(),
+                    verdict: Verdict::TooHigh,
+                    timestamp: 1_701_938_000,
+                },
+                Submission {
+                    year: 2023,
+                    day: day!(8),
+                    part: 2,
+                    answer: "6".into(),
+                    verdict: Verdict::AlreadySolved,
+                    timestamp: 1_702_022_400,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_lines() {
+        let submission = Submission {
+            year: 2023,
+            day: day!(3),
+            part: 2,
+            answer: "multi\nline\tanswer\\".into(),
+            verdict: Verdict::TooRecent(None),
+            timestamp: 1_701_590_400,
+        };
+
+        let line = submission.to_line();
+        assert_eq!(line.lines().count(), 1);
+        assert_eq!(Submission::from_line(&line).unwrap(), submission);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse("2023\t07\t1\t42\n").is_err());
+        assert!(parse("2023\t99\t1\t42\tcorrect\t0\n").is_err());
+        assert!(parse("2023\t07\t1\t42\tmaybe\t0\n").is_err());
+        assert_eq!(parse("\n\n").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn finds_rejected_answers() {
+        let ledger = get_mock_ledger();
+
+        assert!(ledger
+            .find_rejected(2023, day!(7), 1, "250120186")
+            .is_some());
+        assert!(ledger
+            .find_rejected(2023, day!(7), 1, "250254244")
+            .is_none());
+        assert!(ledger
+            .find_rejected(2023, day!(7), 2, "250120186")
+            .is_none());
+        assert!(ledger.find_rejected(2023, day!(7), 1, "42").is_none());
+        assert_eq!(ledger.history(2023, day!(7), 1).len(), 2);
+        assert!(ledger.is_solved(2023, day!(7), 1));
+        assert!(!ledger.is_solved(2023, day!(7), 2));
+        assert!(!ledger.is_solved(2022, day!(7), 1));
+    }
+
+    #[test]
+    fn loads_days_of_every_year() {
+        let entries = parse("2023\t20\t1\t42\tcorrect\t0\n2025\t03\t1\t7\tincorrect\t1\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].day, day!(20));
+    }
+
+    #[test]
+    fn ignores_wrong_level_submissions() {
+        let ledger = get_mock_ledger();
+
+        assert_eq!(ledger.history(2023, day!(8), 2).len(), 1);
+        assert!(!ledger.is_solved(2023, day!(8), 2));
+    }
+
+    #[test]
+    fn finds_out_of_bounds_answers() {
+        let ledger = get_mock_ledger();
+
+        assert!(ledger.find_out_of_bounds(2023, day!(7), 2, "999").is_some());
+        assert!(ledger
+            .find_out_of_bounds(2023, day!(7), 2, "1000")
+            .is_some());
+        assert!(ledger
+            .find_out_of_bounds(2023, day!(7), 2, "5001")
+            .is_some());
+        assert!(ledger
+            .find_out_of_bounds(2023, day!(7), 2, "1001")
+            .is_none());
+        assert!(ledger.find_out_of_bounds(2023, day!(7), 2, "abc").is_none());
+        assert!(ledger.find_out_of_bounds(2023, day!(7), 1, "1").is_none());
+    }
+}
@@ -0,0 +1,244 @@
+/// Timestamps of the milestones of every day: download, first submission and first correct answer per part.
+/// Only the first occurrence of each milestone is kept, so re-downloading a day does not reset its timer.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::Day;
+
+pub const TIMELINE_PATH: &str = "data/timeline.tsv";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Downloaded,
+    Submitted(u8),
+    Solved(u8),
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Downloaded => write!(f, "downloaded"),
+            Event::Submitted(part) => write!(f, "submitted-{part}"),
+            Event::Solved(part) => write!(f, "solved-{part}"),
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_err = || Error::Parser(format!("unknown timeline event: {s}"));
+        let part = |p: &str| p.parse().map_err(|_| parse_err());
+
+        match s.split_once('-') {
+            None if s == "downloaded" => Ok(Event::Downloaded),
+            Some(("submitted", p)) => Ok(Event::Submitted(part(p)?)),
+            Some(("solved", p)) => Ok(Event::Solved(part(p)?)),
+            _ => Err(parse_err()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    year: u16,
+    day: Day,
+    event: Event,
+    /// Unix timestamp (seconds).
+    timestamp: i64,
+}
+
+/// How long a day took, measured from its download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayTimes {
+    pub downloaded: i64,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+pub struct Timeline {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Timeline {
+    /// Loads the timeline at `path`. A missing file is treated as an empty timeline.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => parse(&contents)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn get(&self, year: u16, day: Day, event: Event) -> Option<i64> {
+        self.entries
+            .iter()
+            .find(|e| e.year == year && e.day == day && e.event == event)
+            .map(|e| e.timestamp)
+    }
+
+    /// Appends the event unless it was recorded before. Returns whether it was new.
+    pub fn record(
+        &mut self,
+        year: u16,
+        day: Day,
+        event: Event,
+        timestamp: i64,
+    ) -> Result<bool, Error> {
+        if self.get(year, day, event).is_some() {
+            return Ok(false);
+        }
+
+        let entry = Entry {
+            year,
+            day,
+            event,
+            timestamp,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            entry.year, entry.day, entry.event, entry.timestamp
+        )?;
+
+        self.entries.push(entry);
+        Ok(true)
+    }
+
+    /// Time from the download of a day to the first correct answer of each part.
+    pub fn day_times(&self, year: u16, day: Day) -> Option<DayTimes> {
+        let downloaded = self.get(year, day, Event::Downloaded)?;
+        let since_download = |part| {
+            let solved = self.get(year, day, Event::Solved(part))?;
+            Some(Duration::from_secs(
+                u64::try_from(solved - downloaded).unwrap_or(0),
+            ))
+        };
+
+        Some(DayTimes {
+            downloaded,
+            part_one: since_download(1),
+            part_two: since_download(2),
+        })
+    }
+}
+
+/// Records an event in the default timeline at the current time.
+pub fn record_now(year: u16, day: Day, event: Event) -> Result<bool, Error> {
+    Timeline::load(TIMELINE_PATH)?.record(year, day, event, Utc::now().timestamp())
+}
+
+fn parse(contents: &str) -> Result<Vec<Entry>, Error> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parse_err = || Error::Parser(format!("malformed timeline line: {line}"));
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            let [year, day, event, timestamp] = fields[..] else {
+                return Err(parse_err());
+            };
+
+            Ok(Entry {
+                year: year.parse().map_err(|_| parse_err())?,
+                day: day.parse().map_err(|_| parse_err())?,
+                event: event.parse()?,
+                timestamp: timestamp.parse().map_err(|_| parse_err())?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, DayTimes, Event, Timeline};
+    use crate::day;
+    use std::time::Duration;
+
+    fn get_mock_timeline() -> Timeline {
+        Timeline {
+            path: "unused.tsv".into(),
+            entries: parse(
+                "2023\t07\tdownloaded\t1000\n2023\t07\tsubmitted-1\t1500\n2023\t07\tsolved-1\t1600\n",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn parses_events() {
+        assert_eq!("downloaded".parse::<Event>().unwrap(), Event::Downloaded);
+        assert_eq!("solved-2".parse::<Event>().unwrap(), Event::Solved(2));
+        assert_eq!(Event::Submitted(1).to_string(), "submitted-1");
+        assert!("solved".parse::<Event>().is_err());
+        assert!(parse("2023\t07\tdownloaded\n").is_err());
+    }
+
+    #[test]
+    fn measures_days_from_download() {
+        let timeline = get_mock_timeline();
+
+        assert_eq!(
+            timeline.day_times(2023, day!(7)),
+            Some(DayTimes {
+                downloaded: 1000,
+                part_one: Some(Duration::from_secs(600)),
+                part_two: None,
+            })
+        );
+        assert_eq!(timeline.day_times(2023, day!(8)), None);
+        assert_eq!(timeline.get(2023, day!(7), Event::Submitted(1)), Some(1500));
+    }
+
+    #[test]
+    fn keeps_first_occurrence() {
+        let path = std::env::temp_dir().join(format!("aoc-timeline-{}.tsv", std::process::id()));
+        let mut timeline = Timeline::load(&path).unwrap();
+
+        assert!(timeline
+            .record(2023, day!(1), Event::Downloaded, 10)
+            .unwrap());
+        assert!(!timeline
+            .record(2023, day!(1), Event::Downloaded, 20)
+            .unwrap());
+
+        let reloaded = Timeline::load(&path).unwrap();
+        assert_eq!(reloaded.get(2023, day!(1), Event::Downloaded), Some(10));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+/// Registry of puzzle titles, read from the descriptions cached in `data/{year}/puzzles`.
+use std::{collections::BTreeMap, fs};
+
+use crate::template::{aoc_client, puzzle::Puzzle};
+use crate::{all_days_of, Day};
+
+#[derive(Debug, Clone, Default)]
+pub struct Titles {
+    titles: BTreeMap<Day, String>,
+}
+
+impl Titles {
+    /// Reads the titles of every day with a cached description.
+    pub fn load(year: u16) -> Self {
+        let titles = all_days_of(year)
+            .filter_map(|day| {
+                let markdown = fs::read_to_string(aoc_client::get_puzzle_path(year, day)).ok()?;
+                Some((day, parse_title(&markdown)?))
+            })
+            .collect();
+
+        Self { titles }
+    }
+
+    /// The title of a day without decoration, e.g. `Camel Cards`.
+    pub fn get(&self, day: Day) -> Option<&str> {
+        self.titles.get(&day).map(String::as_str)
+    }
+
+    /// A heading like `Day 07: Camel Cards`, or `Day 07` if the title is unknown.
+    pub fn heading(&self, day: Day) -> String {
+        match self.get(day) {
+            Some(title) => format!("Day {day}: {title}"),
+            None => format!("Day {day}"),
+        }
+    }
+}
+
+impl FromIterator<(Day, String)> for Titles {
+    fn from_iter<I: IntoIterator<Item = (Day, String)>>(iter: I) -> Self {
+        Self {
+            titles: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Extracts the title from the first heading of a description, e.g. `--- Day 7: Camel Cards ---`.
+fn parse_title(markdown: &str) -> Option<String> {
+    let puzzle = Puzzle::parse(markdown);
+    let heading = puzzle.sections.first()?.heading.as_str();
+
+    let title = heading
+        .trim_matches(|c: char| c == '-' || c.is_whitespace())
+        .split_once(':')?
+        .1
+        .trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, Titles};
+    use crate::day;
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 7: Camel Cards ---\n\nYour all-expenses-paid trip...\n"),
+            Some("Camel Cards".into())
+        );
+        assert_eq!(
+            parse_title("\\--- Day 1: Trebuchet?! ---\n----------\n"),
+            Some("Trebuchet?!".into())
+        );
+        assert_eq!(
+            parse_title("## --- Day 2: Cube Conundrum: Part 1 ---\n"),
+            Some("Cube Conundrum: Part 1".into())
+        );
+        assert_eq!(parse_title("Just some text.\n"), None);
+    }
+
+    #[test]
+    fn formats_headings() {
+        let titles: Titles = [(day!(7), "Camel Cards".to_string())].into_iter().collect();
+
+        assert_eq!(titles.heading(day!(7)), "Day 07: Camel Cards");
+        assert_eq!(titles.heading(day!(8)), "Day 08");
+    }
+}
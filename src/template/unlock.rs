@@ -0,0 +1,107 @@
+/// Puzzle unlock times. A day unlocks at midnight EST (UTC-5) on the matching day of December.
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use crate::{all_days_of, Day};
+
+/// Source of the current time, so unlock calculations can be tested with a fixed clock.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// The time zone puzzles unlock in.
+fn eastern() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).unwrap()
+}
+
+/// The moment the puzzle of `day` unlocks in `year`.
+pub fn unlock_time(year: u16, day: Day) -> DateTime<Utc> {
+    let date = NaiveDate::from_ymd_opt(i32::from(year), 12, u32::from(day.into_inner())).unwrap();
+    eastern()
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// The next day of `year` to unlock and the time until it does. [`None`] if every day is unlocked.
+pub fn next_unlock(year: u16, clock: &impl Clock) -> Option<(Day, Duration)> {
+    let now = clock.now();
+    all_days_of(year)
+        .map(|day| (day, unlock_time(year, day) - now))
+        .find(|(_, remaining)| *remaining > Duration::zero())
+}
+
+/// The most recently unlocked day of `year`, if `year`'s event is currently running.
+pub fn today(year: u16, clock: &impl Clock) -> Option<Day> {
+    let now = clock.now();
+    let day = all_days_of(year)
+        .take_while(|day| unlock_time(year, *day) <= now)
+        .last()?;
+
+    // the last puzzle stays "today" until the day after it unlocked.
+    (now - unlock_time(year, day) < Duration::days(1)).then_some(day)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{next_unlock, today, unlock_time, Clock};
+    use crate::day;
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    struct FixedClock(DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> FixedClock {
+        FixedClock(
+            Utc.with_ymd_and_hms(2023, month, day, hour, minute, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        assert_eq!(
+            unlock_time(2023, day!(1)),
+            Utc.with_ymd_and_hms(2023, 12, 1, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(
+            next_unlock(2023, &at(11, 30, 20, 0)),
+            Some((day!(1), Duration::hours(9)))
+        );
+        assert_eq!(
+            next_unlock(2023, &at(12, 8, 4, 59)),
+            Some((day!(8), Duration::minutes(1)))
+        );
+        assert_eq!(
+            next_unlock(2023, &at(12, 8, 5, 0)),
+            Some((day!(9), Duration::days(1)))
+        );
+        assert_eq!(next_unlock(2023, &at(12, 25, 6, 0)), None);
+    }
+
+    #[test]
+    fn resolves_today() {
+        assert_eq!(today(2023, &at(11, 30, 20, 0)), None);
+        assert_eq!(today(2023, &at(12, 1, 5, 0)), Some(day!(1)));
+        assert_eq!(today(2023, &at(12, 9, 4, 59)), Some(day!(8)));
+        assert_eq!(today(2023, &at(12, 26, 4, 59)), Some(day!(25)));
+        assert_eq!(today(2023, &at(12, 26, 5, 0)), None);
+    }
+}
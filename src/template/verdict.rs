@@ -0,0 +1,203 @@
+/// Structured result of submitting an answer, derived from the response text.
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    AlreadySolved,
+    /// The answer was not checked. Contains the remaining wait time, if the response stated one.
+    TooRecent(Option<Duration>),
+    Unknown(String),
+}
+
+lazy_static! {
+    static ref WAIT_LEFT: Regex = Regex::new(r"you have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref WAIT_MINUTES: Regex = Regex::new(r"wait (\d+|one) minutes?").unwrap();
+}
+
+impl Verdict {
+    /// Derives the verdict from the message of a submission response.
+    pub fn from_response(message: &str) -> Self {
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Verdict::TooHigh
+            } else if message.contains("too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if message.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooRecent(parse_wait(message))
+        } else {
+            Verdict::Unknown(message.trim().into())
+        }
+    }
+
+    /// Whether the site checked the answer and rejected it.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    /// The key used to store the verdict in the submission ledger.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::TooRecent(_) => "too-recent",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+}
+
+/// Reads the wait time from e.g. "You have 5m 2s left to wait" or "please wait 5 minutes".
+fn parse_wait(message: &str) -> Option<Duration> {
+    let message = message.to_lowercase();
+
+    if let Some(c) = WAIT_LEFT.captures(&message) {
+        let minutes: u64 = c.get(1).map_or(Some(0), |m| m.as_str().parse().ok())?;
+        let seconds: u64 = c[2].parse().ok()?;
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    if let Some(c) = WAIT_MINUTES.captures(&message) {
+        let minutes: u64 = match &c[1] {
+            "one" => 1,
+            n => n.parse().ok()?,
+        };
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    None
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct ⭐"),
+            Verdict::TooHigh => write!(f, "incorrect, too high"),
+            Verdict::TooLow => write!(f, "incorrect, too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::TooRecent(Some(wait)) => {
+                write!(f, "answered too recently, wait {}s", wait.as_secs())
+            }
+            Verdict::TooRecent(None) => write!(f, "answered too recently"),
+            Verdict::Unknown(message) => write!(f, "unknown response: {message}"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Verdict`] key.
+#[derive(Debug)]
+pub struct VerdictFromStrError;
+
+impl FromStr for Verdict {
+    type Err = VerdictFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            "too-recent" => Ok(Verdict::TooRecent(None)),
+            "unknown" => Ok(Verdict::Unknown(String::new())),
+            _ => Err(VerdictFromStrError),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Verdict;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            Verdict::from_response("Something else entirely."),
+            Verdict::Unknown("Something else entirely.".into())
+        );
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 5m 2s left to wait."),
+            Verdict::TooRecent(Some(Duration::from_secs(302)))
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently. You have 42s left to wait."),
+            Verdict::TooRecent(Some(Duration::from_secs(42)))
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You gave an answer too recently. Please wait one minute before trying again."
+            ),
+            Verdict::TooRecent(Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "You gave an answer too recently; please wait 5 minutes before trying again."
+            ),
+            Verdict::TooRecent(Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently. Please wait 2 minutes."),
+            Verdict::TooRecent(Some(Duration::from_secs(120)))
+        );
+    }
+
+    #[test]
+    fn round_trips_keys() {
+        for verdict in [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Incorrect,
+            Verdict::AlreadySolved,
+        ] {
+            assert_eq!(verdict.key().parse::<Verdict>().unwrap(), verdict);
+        }
+        assert!("nope".parse::<Verdict>().is_err());
+    }
+}